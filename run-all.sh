@@ -1,8 +1,8 @@
 #! /bin/bash
 
-# start local validator
+# start local validator, preloaded with the legacy accounts the migration tests upgrade
 echo "Starting local validator..."
-solana-test-validator --reset &
+solana-test-validator --reset --account-dir tests/fixtures &
 
 # wait for validator to start
 sleep 10
@@ -11,8 +11,8 @@ pub enum RatingsErrors {
     InvalidAssociatedTokenAccount,
     #[error("Invalid owner")]
     InvalidOwner,
-    #[error("Movie title too long")]
-    MovieTitleTooLong,
+    #[error("Item title too long")]
+    ItemTitleTooLong,
     #[error("Invalid rating value")]
     InvalidRatingValue,
     #[error("Expected empty account")]
@@ -21,6 +21,72 @@ pub enum RatingsErrors {
     InvalidMintAccount,
     #[error("Invalid instruction")]
     InvalidInstruction,
+    #[error("Review too long")]
+    ReviewTooLong,
+    #[error("Invalid Item account")]
+    InvalidItemAccount,
+    #[error("Item is not registered")]
+    ItemNotRegistered,
+    #[error("Invalid admin authority")]
+    InvalidAuthority,
+    #[error("Invalid rating scale")]
+    InvalidRatingScale,
+    #[error("Invalid rating dimensions")]
+    InvalidRatingDimensions,
+    #[error("Invalid sub-ratings")]
+    InvalidSubRatings,
+    #[error("Invalid User account")]
+    InvalidUserAccount,
+    #[error("Invalid Vote account")]
+    InvalidVoteAccount,
+    #[error("Rating already voted on by this voter")]
+    AlreadyVoted,
+    #[error("Cannot vote on your own rating")]
+    CannotVoteOnOwnRating,
+    #[error("Invalid item kind")]
+    InvalidItemKind,
+    #[error("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[error("Invalid pending authority")]
+    InvalidPendingAuthority,
+    #[error("Program is paused")]
+    ProgramPaused,
+    #[error("Invalid multisig configuration")]
+    InvalidMultisig,
+    #[error("Not enough multisig signers")]
+    NotEnoughSigners,
+    #[error("Change must be queued through the timelock")]
+    TimelockActive,
+    #[error("Timelock delay has not passed")]
+    TimelockNotElapsed,
+    #[error("Invalid Pending Change account")]
+    InvalidPendingChangeAccount,
+    #[error("Invalid Moderator account")]
+    InvalidModeratorAccount,
+    #[error("Rating has been hidden by moderation")]
+    RatingHidden,
+    #[error("Invalid moderation rent destination")]
+    InvalidModerationDestination,
+    #[error("Invalid Ban account")]
+    InvalidBanAccount,
+    #[error("User is banned from rating")]
+    UserBanned,
+    #[error("Invalid Allowlist account")]
+    InvalidAllowlistAccount,
+    #[error("User is not on the catalog allowlist")]
+    NotAllowlisted,
+    #[error("Account uses an older layout and must be migrated")]
+    AccountNeedsMigration,
+    #[error("Account is of another type")]
+    InvalidAccountType,
+    #[error("Reward would exceed the supply cap")]
+    SupplyCapExceeded,
+    #[error("Catalog was closed and its ID can't be reused")]
+    CatalogClosed,
+    #[error("Rating has been removed by moderation")]
+    RatingRemoved,
+    #[error("Account already uses the current layout")]
+    AccountAlreadyMigrated,
 }
 
 impl From<RatingsErrors> for ProgramError {
@@ -0,0 +1,68 @@
+use crate::{AdminAccount, AdminState, RatingsErrors, SignerAccount};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct AcceptAuthorityAccounts<'a> {
+    pub new_authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [new_authority, admin] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            new_authority,
+            admin,
+        })
+    }
+}
+
+pub struct AcceptAuthority<'a> {
+    pub accounts: AcceptAuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: AcceptAuthorityAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> AcceptAuthority<'a> {
+    pub const DISCRIMINATOR: u8 = 9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.new_authority)?;
+        msg!("Checked if new authority is signer");
+
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+
+        if !admin_data.has_pending_authority() {
+            return Err(RatingsErrors::NoPendingAuthority.into());
+        }
+        if &admin_data.pending_authority != self.accounts.new_authority.key() {
+            return Err(RatingsErrors::InvalidPendingAuthority.into());
+        }
+
+        admin_data.set_authority(*self.accounts.new_authority.key())?;
+        admin_data.set_pending_authority(Pubkey::default())?;
+        msg!("Authority transfer accepted");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    AdminAccount, AdminState, AllowlistAccount, AllowlistState, SignerAccount, SystemProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct AddToAllowlistAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub allowlist_entries: &'a [AccountInfo],
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], usize)> for AddToAllowlistAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], usize)) -> Result<Self, Self::Error> {
+        // The first `entry_count` trailing accounts are the entries, any after them are signers
+        let (accounts, entry_count) = input;
+        let [authority, admin, payer, system_program, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let Some((allowlist_entries, signers)) = remaining.split_at_checked(entry_count) else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            payer,
+            system_program,
+            allowlist_entries,
+            signers,
+        })
+    }
+}
+
+pub struct AddToAllowlistPayload {
+    pub users: Vec<Pubkey>,
+}
+
+impl TryFrom<&[u8]> for AddToAllowlistPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [users: Pubkey * n], one allowlist entry account per user in the same order
+        if data.is_empty() || !data.len().is_multiple_of(32) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let users: Vec<Pubkey> = data
+            .chunks_exact(32)
+            .map(|user| {
+                user.try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { users })
+    }
+}
+
+pub struct AddToAllowlist<'a> {
+    pub accounts: AddToAllowlistAccounts<'a>,
+    pub payload: AddToAllowlistPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for AddToAllowlist<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let payload: AddToAllowlistPayload = AddToAllowlistPayload::try_from(data)?;
+        let accounts: AddToAllowlistAccounts<'_> =
+            AddToAllowlistAccounts::try_from((accounts, payload.users.len()))?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> AddToAllowlist<'a> {
+    pub const DISCRIMINATOR: u8 = 21;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.payer)?;
+        msg!("Checked if payer is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        drop(admin_data);
+        msg!("Admin authority validated");
+
+        let lamports: u64 = Rent::get()?.minimum_balance(AllowlistState::LEN);
+
+        for (user, allowlist_entry) in self
+            .payload
+            .users
+            .iter()
+            .zip(self.accounts.allowlist_entries.iter())
+        {
+            let allowlist_bump: u8 = AllowlistAccount::check_is_valid_allowlist(
+                allowlist_entry,
+                self.accounts.admin.key(),
+                user,
+            )?;
+
+            // Re-adding a wallet is a no-op so a cohort can be resubmitted after a partial failure
+            if !allowlist_entry.data_is_empty() {
+                msg!("User already allowlisted");
+                continue;
+            }
+
+            let bump_slice: [u8; 1] = [allowlist_bump];
+            let allowlist_seeds: [Seed<'_>; 4] = [
+                Seed::from(b"allowlist"),
+                Seed::from(self.accounts.admin.key().as_ref()),
+                Seed::from(user.as_ref()),
+                Seed::from(&bump_slice),
+            ];
+
+            CreateAccount {
+                from: self.accounts.payer,
+                to: allowlist_entry,
+                lamports,
+                space: AllowlistState::LEN as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&[Signer::from(&allowlist_seeds)])?;
+
+            let allowlist_state: AllowlistState = AllowlistState::set_inner(
+                *self.accounts.admin.key(),
+                *user,
+                allowlist_bump,
+                *self.accounts.payer.key(),
+            )?;
+            let mut allowlist_data: RefMut<'_, [u8]> = allowlist_entry.try_borrow_mut_data()?;
+            allowlist_data.copy_from_slice(allowlist_state.as_ref());
+            msg!("User allowlisted");
+        }
+
+        Ok(())
+    }
+}
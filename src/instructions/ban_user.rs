@@ -0,0 +1,135 @@
+use crate::{
+    AdminAccount, AdminState, BanAccount, BanState, ModeratorAccount, SystemProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct BanUserAccounts<'a> {
+    pub moderator: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub moderator_account: &'a AccountInfo,
+    pub user: &'a AccountInfo,
+    pub ban: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for BanUserAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [moderator, admin, moderator_account, user, ban, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            moderator,
+            admin,
+            moderator_account,
+            user,
+            ban,
+            system_program,
+        })
+    }
+}
+
+pub struct BanUserPayload {
+    pub reason: u8,
+}
+
+impl TryFrom<&[u8]> for BanUserPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [reason: u8]
+        let [reason] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self { reason: *reason })
+    }
+}
+
+pub struct BanUser<'a> {
+    pub accounts: BanUserAccounts<'a>,
+    pub payload: BanUserPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for BanUser<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: BanUserAccounts<'_> = BanUserAccounts::try_from(accounts)?;
+        let payload: BanUserPayload = BanUserPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> BanUser<'a> {
+    pub const DISCRIMINATOR: u8 = 19;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        ModeratorAccount::check_is_admin_or_moderator(
+            &admin_data,
+            self.accounts.admin,
+            self.accounts.moderator,
+            self.accounts.moderator_account,
+        )?;
+        drop(admin_data);
+        msg!("Moderator validated");
+
+        let ban_bump: u8 = BanAccount::check_is_valid_ban(
+            self.accounts.ban,
+            self.accounts.admin.key(),
+            self.accounts.user.key(),
+        )?;
+        msg!("Ban account validated");
+        BanAccount::check_is_not_banned(self.accounts.ban)?;
+        msg!("User is not already banned");
+
+        // The moderator signs anyway, so they fund the ban and get the rent back on unban
+        let bump_slice: [u8; 1] = [ban_bump];
+        let ban_seeds: [Seed<'_>; 4] = [
+            Seed::from(b"ban"),
+            Seed::from(self.accounts.admin.key().as_ref()),
+            Seed::from(self.accounts.user.key().as_ref()),
+            Seed::from(&bump_slice),
+        ];
+
+        CreateAccount {
+            from: self.accounts.moderator,
+            to: self.accounts.ban,
+            lamports: Rent::get()?.minimum_balance(BanState::LEN),
+            space: BanState::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&ban_seeds)])?;
+        msg!("Ban account created");
+
+        let ban_state: BanState = BanState::set_inner(
+            *self.accounts.admin.key(),
+            *self.accounts.user.key(),
+            *self.accounts.moderator.key(),
+            Clock::get()?.unix_timestamp,
+            self.payload.reason,
+            ban_bump,
+        )?;
+        let mut ban_data: RefMut<'_, [u8]> = self.accounts.ban.try_borrow_mut_data()?;
+        ban_data.copy_from_slice(ban_state.as_ref());
+        msg!("User banned");
+
+        Ok(())
+    }
+}
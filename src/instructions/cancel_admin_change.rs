@@ -0,0 +1,87 @@
+use crate::{AdminAccount, AdminState, PendingChangeAccount, PendingChangeState};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+pub struct CancelAdminChangeAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub pending_change: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAdminChangeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, pending_change, payer, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            pending_change,
+            payer,
+            signers,
+        })
+    }
+}
+
+pub struct CancelAdminChange<'a> {
+    pub accounts: CancelAdminChangeAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAdminChange<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CancelAdminChangeAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CancelAdminChange<'a> {
+    pub const DISCRIMINATOR: u8 = 15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        drop(admin_data);
+        msg!("Admin authority validated");
+        PendingChangeAccount::check_is_valid_pending_change(
+            self.accounts.pending_change,
+            self.accounts.admin,
+        )?;
+        let pending_change: Ref<'_, PendingChangeState> =
+            PendingChangeState::load(self.accounts.pending_change)?;
+        msg!("Pending change account validated");
+
+        if &pending_change.payer != self.accounts.payer.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        drop(pending_change);
+
+        let pending_change_lamports: u64 = self.accounts.pending_change.lamports();
+
+        *self.accounts.pending_change.try_borrow_mut_lamports()? -= pending_change_lamports;
+        *self.accounts.payer.try_borrow_mut_lamports()? += pending_change_lamports;
+        msg!("Transferred lamports back to payer");
+
+        self.accounts.pending_change.close()?;
+        msg!("Admin change cancelled");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{AdminAccount, AdminState, RatingsErrors};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct CancelAuthorityTransferAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAuthorityTransferAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            signers,
+        })
+    }
+}
+
+pub struct CancelAuthorityTransfer<'a> {
+    pub accounts: CancelAuthorityTransferAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAuthorityTransfer<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CancelAuthorityTransferAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CancelAuthorityTransfer<'a> {
+    pub const DISCRIMINATOR: u8 = 10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        if !admin_data.has_pending_authority() {
+            return Err(RatingsErrors::NoPendingAuthority.into());
+        }
+
+        admin_data.set_pending_authority(Pubkey::default())?;
+        msg!("Authority transfer cancelled");
+
+        Ok(())
+    }
+}
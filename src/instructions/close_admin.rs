@@ -0,0 +1,226 @@
+use crate::{
+    AdminAccount, AdminState, AssociatedTokenAccount, ClosedAdminState, MintAccount,
+    PendingChangeAccount, RatingsErrors, TokenProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::{AuthorityType, CloseAccount, SetAuthority, TransferChecked};
+use pinocchio_token::state::{Mint, TokenAccount as PinoTokenAccount};
+
+/// What happens to the mint authority held by the admin PDA, stored as the first payload byte
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintAuthorityAction {
+    Keep = 0,     // Leave it with the closed admin PDA
+    Revoke = 1,   // Fix the supply for good
+    Transfer = 2, // Hand it to the key that follows in the payload
+}
+
+pub struct CloseAdminAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub pending_change: &'a AccountInfo,
+    pub admin_ata: &'a AccountInfo,
+    pub ratings_mint: &'a AccountInfo,
+    pub destination_token_account: &'a AccountInfo,
+    pub rent_destination: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseAdminAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, pending_change, admin_ata, ratings_mint, destination_token_account, rent_destination, token_program, signers @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            pending_change,
+            admin_ata,
+            ratings_mint,
+            destination_token_account,
+            rent_destination,
+            token_program,
+            signers,
+        })
+    }
+}
+
+pub struct CloseAdminPayload {
+    pub mint_authority_action: MintAuthorityAction,
+    pub new_mint_authority: Option<Pubkey>,
+}
+
+impl TryFrom<&[u8]> for CloseAdminPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [mint_authority_action: u8][new_mint_authority: Pubkey (transfer only)]
+        match data {
+            [0] => Ok(Self {
+                mint_authority_action: MintAuthorityAction::Keep,
+                new_mint_authority: None,
+            }),
+            [1] => Ok(Self {
+                mint_authority_action: MintAuthorityAction::Revoke,
+                new_mint_authority: None,
+            }),
+            [2, new_mint_authority @ ..] => {
+                let new_mint_authority: Pubkey = new_mint_authority
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                if new_mint_authority == Pubkey::default() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(Self {
+                    mint_authority_action: MintAuthorityAction::Transfer,
+                    new_mint_authority: Some(new_mint_authority),
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct CloseAdmin<'a> {
+    pub accounts: CloseAdminAccounts<'a>,
+    pub payload: CloseAdminPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for CloseAdmin<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: CloseAdminAccounts<'_> = CloseAdminAccounts::try_from(accounts)?;
+        let payload: CloseAdminPayload = CloseAdminPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> CloseAdmin<'a> {
+    pub const DISCRIMINATOR: u8 = 23;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        // Closing can't be queued, so it's rejected outright while a timelock delay is set.
+        // Queueing the delay back to 0 first still gives users the notice
+        if admin_data.is_timelocked() {
+            return Err(RatingsErrors::TimelockActive.into());
+        }
+
+        // A queued change has to be cancelled first so its payer gets the rent back
+        PendingChangeAccount::check_is_valid_pending_change(
+            self.accounts.pending_change,
+            self.accounts.admin,
+        )?;
+        PendingChangeAccount::check_is_empty(self.accounts.pending_change)?;
+        msg!("No pending change");
+
+        TokenProgramAccount::check_is_token_program(self.accounts.token_program)?;
+        msg!("Token program validated");
+        MintAccount::check_is_mint(self.accounts.ratings_mint, &admin_data.token_mint)?;
+        msg!("Mint account validated");
+        AssociatedTokenAccount::check_is_valid_ata(
+            self.accounts.admin_ata,
+            self.accounts.admin,
+            self.accounts.ratings_mint,
+        )?;
+        msg!("Admin ATA account validated");
+
+        let authority: Pubkey = admin_data.authority;
+        let catalog_id_bytes: [u8; 8] = admin_data.catalog_id.to_le_bytes();
+        let bump_slice: [u8; 1] = [admin_data.bump];
+        drop(admin_data);
+        let admin_seeds: [Seed<'_>; 3] = [
+            Seed::from(b"ratings_admin"),
+            Seed::from(&catalog_id_bytes),
+            Seed::from(&bump_slice),
+        ];
+
+        // Drain the treasury, the token program checks the destination mint
+        let treasury_balance: u64 =
+            PinoTokenAccount::from_account_info(self.accounts.admin_ata)?.amount();
+        if treasury_balance > 0 {
+            let decimals: u8 = Mint::from_account_info(self.accounts.ratings_mint)?.decimals();
+            TransferChecked {
+                from: self.accounts.admin_ata,
+                mint: self.accounts.ratings_mint,
+                to: self.accounts.destination_token_account,
+                authority: self.accounts.admin,
+                amount: treasury_balance,
+                decimals,
+            }
+            .invoke_signed(&[Signer::from(&admin_seeds)])?;
+            msg!("Treasury transferred to destination");
+        }
+
+        match self.payload.mint_authority_action {
+            MintAuthorityAction::Keep => {}
+            MintAuthorityAction::Revoke | MintAuthorityAction::Transfer => {
+                SetAuthority {
+                    account: self.accounts.ratings_mint,
+                    authority: self.accounts.admin,
+                    authority_type: AuthorityType::MintTokens,
+                    new_authority: self.payload.new_mint_authority.as_ref(),
+                }
+                .invoke_signed(&[Signer::from(&admin_seeds)])?;
+                msg!("Mint authority updated");
+            }
+        }
+
+        CloseAccount {
+            account: self.accounts.admin_ata,
+            destination: self.accounts.rent_destination,
+            authority: self.accounts.admin,
+        }
+        .invoke_signed(&[Signer::from(&admin_seeds)])?;
+        msg!("Admin ATA closed");
+
+        // A tombstone stays behind so the catalog ID can't be initialized again and inherit
+        // the moderator, ban, allowlist, item and rating accounts seeded from it. It keeps the
+        // last authority, who can still revoke, unban and remove entries for their rent
+        self.accounts.admin.resize(ClosedAdminState::LEN)?;
+        self.accounts.admin.try_borrow_mut_data()?[..ClosedAdminState::LEN]
+            .copy_from_slice(ClosedAdminState::set_inner(authority)?.as_ref());
+        msg!("Admin account closed");
+
+        // The tombstone keeps its own rent on purpose, without it the account would be
+        // garbage collected and the catalog ID freed
+        let admin_lamports: u64 = self
+            .accounts
+            .admin
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(ClosedAdminState::LEN));
+
+        *self.accounts.admin.try_borrow_mut_lamports()? -= admin_lamports;
+        *self.accounts.rent_destination.try_borrow_mut_lamports()? += admin_lamports;
+        msg!("Transferred lamports to rent destination");
+
+        Ok(())
+    }
+}
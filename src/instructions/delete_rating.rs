@@ -1,8 +1,10 @@
-use crate::{RatingAccount, RatingState, SignerAccount, SystemProgramAccount};
+use crate::{
+    AdminAccount, AdminState, ItemAccount, ItemState, RatingAccount, RatingState, RatingsErrors,
+    SignerAccount, SystemProgramAccount, UserAccount, UserState,
+};
 use pinocchio::msg;
 use pinocchio::{
-    account_info::{AccountInfo, Ref},
-    instruction::{Seed, Signer},
+    account_info::{AccountInfo, Ref, RefMut},
     program_error::ProgramError,
     ProgramResult,
 };
@@ -10,21 +12,27 @@ use pinocchio::{
 pub struct DeleteRatingAccounts<'a> {
     pub authority: &'a AccountInfo,
     pub rating: &'a AccountInfo,
+    pub item: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
+    pub user: &'a AccountInfo,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for DeleteRatingAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [authority, rating, system_program] = accounts else {
+        let [authority, rating, item, admin, system_program, user] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
         Ok(Self {
             authority,
             rating,
+            item,
+            admin,
             system_program,
+            user,
         })
     }
 }
@@ -51,26 +59,65 @@ impl<'a> DeleteRating<'a> {
         msg!("Checked if authority is signer");
         SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
         msg!("Checked system program");
+        // A closed catalog only leaves a tombstone at the admin address, but its ratings can
+        // still be deleted for their rent. The rating PDA check below still pins the admin key
+        let mut admin_data: Option<RefMut<'_, AdminState>> =
+            if AdminAccount::is_closed(self.accounts.admin)? {
+                msg!("Admin account is closed");
+                None
+            } else {
+                let admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+                AdminAccount::check_is_valid_admin(self.accounts.admin, admin_data.catalog_id)?;
+                msg!("Admin state loaded");
+                Some(admin_data)
+            };
 
         let rating_data: Ref<'_, RatingState> = RatingState::load(self.accounts.rating)?;
         msg!("Loaded rating data");
 
-        let movie_title_length = rating_data
-            .movie_title
-            .iter()
-            .filter(|val| **val != 0u8)
-            .count();
-
         RatingAccount::check_is_valid_rating(
             self.accounts.rating,
-            self.accounts.authority,
-            &rating_data.movie_title[..movie_title_length],
+            self.accounts.admin.key(),
+            self.accounts.authority.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
         )?;
         msg!("Validated rating account");
+        ItemAccount::check_is_valid_item(
+            self.accounts.item,
+            self.accounts.admin.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated item account");
+        UserAccount::check_is_valid_user(
+            self.accounts.user,
+            self.accounts.admin.key(),
+            self.accounts.authority.key(),
+        )?;
+        msg!("Validated user account");
+
+        // Removed ratings stay to keep their owner from rating the item again, which stops
+        // mattering once the catalog is closed
+        if rating_data.is_removed() && admin_data.is_some() {
+            return Err(RatingsErrors::RatingRemoved.into());
+        }
+
+        // Hidden ratings already left the aggregate and counters when they were moderated
+        if let Some(admin_data) = admin_data.as_mut().filter(|_| !rating_data.is_hidden()) {
+            ItemState::load_mut(self.accounts.item)?.remove_rating(
+                rating_data.rating,
+                rating_data.sub_ratings(),
+                &admin_data.rating_scale,
+            )?;
+            msg!("Removed rating from item aggregate");
+
+            let rater_left: bool = !self.accounts.user.data_is_empty()
+                && UserState::load_mut(self.accounts.user)?.remove_active_rating()?;
+            admin_data.remove_rating(rater_left)?;
+            msg!("Admin counters updated");
+        }
 
-        let bump_slice: [u8; 1] = [rating_data.bump];
-
-        let movie_title_seed: Vec<u8> = rating_data.movie_title[..movie_title_length].to_vec();
         drop(rating_data);
 
         let rating_lamports: u64 = self.accounts.rating.lamports();
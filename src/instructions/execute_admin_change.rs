@@ -0,0 +1,122 @@
+use crate::{
+    AdminAccount, AdminState, PendingChangeAccount, PendingChangeState, RatingsErrors, RewardMode,
+    UpdateAdminConfigPayload,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    msg,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+pub struct ExecuteAdminChangeAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub pending_change: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteAdminChangeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, pending_change, payer] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            admin,
+            pending_change,
+            payer,
+        })
+    }
+}
+
+pub struct ExecuteAdminChange<'a> {
+    pub accounts: ExecuteAdminChangeAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteAdminChange<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ExecuteAdminChangeAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ExecuteAdminChange<'a> {
+    pub const DISCRIMINATOR: u8 = 14;
+
+    // Permissionless, anyone can crank a queued change once its delay has passed
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        PendingChangeAccount::check_is_valid_pending_change(
+            self.accounts.pending_change,
+            self.accounts.admin,
+        )?;
+        let pending_change: Ref<'_, PendingChangeState> =
+            PendingChangeState::load(self.accounts.pending_change)?;
+        msg!("Pending change account validated");
+
+        if &pending_change.payer != self.accounts.payer.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if Clock::get()?.unix_timestamp < pending_change.eta {
+            return Err(RatingsErrors::TimelockNotElapsed.into());
+        }
+        msg!("Timelock delay has passed");
+
+        if pending_change.field_mask & UpdateAdminConfigPayload::REWARD_AMOUNT != 0 {
+            admin_data.set_reward_amount(pending_change.reward_amount)?;
+            msg!("Reward amount updated");
+        }
+
+        if pending_change.field_mask & UpdateAdminConfigPayload::TIMELOCK_DELAY != 0 {
+            admin_data.set_timelock_delay(pending_change.timelock_delay)?;
+            msg!("Timelock delay updated");
+        }
+
+        if pending_change.field_mask & UpdateAdminConfigPayload::MODERATION_DESTINATION != 0 {
+            admin_data.set_moderation_destination(pending_change.moderation_destination)?;
+            msg!("Moderation destination updated");
+        }
+
+        if pending_change.field_mask & UpdateAdminConfigPayload::ALLOWLIST_ONLY != 0 {
+            admin_data.set_allowlist_only(pending_change.allowlist_only != 0)?;
+            msg!("Allowlist mode updated");
+        }
+
+        if pending_change.field_mask & UpdateAdminConfigPayload::REWARD_MODE != 0 {
+            admin_data.set_reward_mode(
+                RewardMode::try_from(pending_change.reward_mode)?,
+                pending_change.supply_cap,
+            )?;
+            msg!("Reward mode updated");
+        }
+
+        // The new authority still has to accept, same as a direct proposal
+        if pending_change.field_mask & UpdateAdminConfigPayload::AUTHORITY != 0 {
+            admin_data.set_pending_authority(pending_change.new_authority)?;
+            msg!("Authority transfer proposed");
+        }
+
+        drop(pending_change);
+        drop(admin_data);
+
+        let pending_change_lamports: u64 = self.accounts.pending_change.lamports();
+
+        *self.accounts.pending_change.try_borrow_mut_lamports()? -= pending_change_lamports;
+        *self.accounts.payer.try_borrow_mut_lamports()? += pending_change_lamports;
+        msg!("Transferred lamports back to payer");
+
+        self.accounts.pending_change.close()?;
+        msg!("Closed pending change account");
+
+        Ok(())
+    }
+}
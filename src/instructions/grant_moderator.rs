@@ -0,0 +1,118 @@
+use crate::{
+    AdminAccount, AdminState, ModeratorAccount, ModeratorState, SignerAccount, SystemProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct GrantModeratorAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub moderator: &'a AccountInfo,
+    pub moderator_account: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GrantModeratorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, moderator, moderator_account, payer, system_program, signers @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            moderator,
+            moderator_account,
+            payer,
+            system_program,
+            signers,
+        })
+    }
+}
+
+pub struct GrantModerator<'a> {
+    pub accounts: GrantModeratorAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GrantModerator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: GrantModeratorAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> GrantModerator<'a> {
+    pub const DISCRIMINATOR: u8 = 16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.payer)?;
+        msg!("Checked if payer is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        drop(admin_data);
+        msg!("Admin authority validated");
+
+        let moderator_bump: u8 = ModeratorAccount::check_is_valid_moderator(
+            self.accounts.moderator_account,
+            self.accounts.admin.key(),
+            self.accounts.moderator.key(),
+        )?;
+        msg!("Moderator account validated");
+        ModeratorAccount::check_is_empty(self.accounts.moderator_account)?;
+        msg!("Moderator is not already granted");
+
+        let bump_slice: [u8; 1] = [moderator_bump];
+        let moderator_seeds: [Seed<'_>; 4] = [
+            Seed::from(b"moderator"),
+            Seed::from(self.accounts.admin.key().as_ref()),
+            Seed::from(self.accounts.moderator.key().as_ref()),
+            Seed::from(&bump_slice),
+        ];
+
+        CreateAccount {
+            from: self.accounts.payer,
+            to: self.accounts.moderator_account,
+            lamports: Rent::get()?.minimum_balance(ModeratorState::LEN),
+            space: ModeratorState::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&moderator_seeds)])?;
+        msg!("Moderator account created");
+
+        let moderator_state: ModeratorState = ModeratorState::set_inner(
+            *self.accounts.admin.key(),
+            *self.accounts.moderator.key(),
+            moderator_bump,
+        )?;
+        let mut moderator_data: RefMut<'_, [u8]> =
+            self.accounts.moderator_account.try_borrow_mut_data()?;
+        moderator_data.copy_from_slice(moderator_state.as_ref());
+        msg!("Moderator granted");
+
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
-use crate::errors::RatingsErrors;
+use crate::{
+    errors::RatingsErrors, AccountType, AdminState, AllowlistState, ClosedAdminState,
+    ModeratorState,
+};
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
-    pubkey::{find_program_address, Pubkey},
+    pubkey::{create_program_address, find_program_address, Pubkey},
     ProgramResult,
 };
 
@@ -19,8 +22,12 @@ impl SignerAccount {
 
 pub struct AdminAccount;
 impl AdminAccount {
-    pub fn check_is_valid_admin(admin_account: &AccountInfo) -> Result<u8, ProgramError> {
-        let (true_admin_key, bump) = find_program_address(&[b"ratings_admin"], &crate::ID);
+    pub fn check_is_valid_admin(
+        admin_account: &AccountInfo,
+        catalog_id: u64,
+    ) -> Result<u8, ProgramError> {
+        let (true_admin_key, bump) =
+            find_program_address(&[b"ratings_admin", &catalog_id.to_le_bytes()], &crate::ID);
         if admin_account.key() != &true_admin_key {
             return Err(RatingsErrors::InvalidAdminAccount.into());
         }
@@ -28,6 +35,28 @@ impl AdminAccount {
         Ok(bump)
     }
 
+    /// Re-derives the admin PDA from the stored catalog ID and bump, cheaper than a bump search
+    pub fn check_is_valid_admin_with_bump(
+        admin_account: &AccountInfo,
+        admin_state: &AdminState,
+    ) -> Result<(), ProgramError> {
+        let true_admin_key: Pubkey = create_program_address(
+            &[
+                b"ratings_admin",
+                &admin_state.catalog_id.to_le_bytes(),
+                &[admin_state.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| RatingsErrors::InvalidAdminAccount)?;
+
+        if admin_account.key() != &true_admin_key {
+            return Err(RatingsErrors::InvalidAdminAccount.into());
+        }
+
+        Ok(())
+    }
+
     pub fn check_is_empty(account: &AccountInfo) -> Result<(), ProgramError> {
         if account.data_is_empty() {
             Ok(())
@@ -35,17 +64,99 @@ impl AdminAccount {
             Err(RatingsErrors::ExpectedEmptyAccount.into())
         }
     }
+
+    /// Returns whether the admin address holds the tombstone `close_admin` leaves behind
+    pub fn is_closed(account: &AccountInfo) -> Result<bool, ProgramError> {
+        Ok(account.owner() == &crate::ID
+            && account.data_len() == ClosedAdminState::LEN
+            && account.try_borrow_data()?[1] == AccountType::ClosedAdmin as u8)
+    }
+
+    pub fn check_is_not_closed(account: &AccountInfo) -> Result<(), ProgramError> {
+        if Self::is_closed(account)? {
+            return Err(RatingsErrors::CatalogClosed.into());
+        }
+
+        Ok(())
+    }
+
+    /// Gate for cleaning up the moderator, ban and allowlist accounts of a closed catalog.
+    /// Multisig went with the admin state, so the last authority alone must sign
+    pub fn check_is_closed_authority(
+        account: &AccountInfo,
+        authority: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if !authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if &ClosedAdminState::load(account)?.authority != authority.key() {
+            return Err(RatingsErrors::InvalidAuthority.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn check_is_authority(
+        admin_state: &AdminState,
+        authority: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if &admin_state.authority != authority.key() {
+            return Err(RatingsErrors::InvalidAuthority.into());
+        }
+
+        Ok(())
+    }
+
+    /// Gate for privileged instructions. Without multisig the authority must sign, with
+    /// multisig at least `multisig_threshold` distinct stored keys must sign across the
+    /// authority account and the extra signer accounts
+    pub fn check_is_privileged(
+        admin_state: &AdminState,
+        authority: &AccountInfo,
+        signers: &[AccountInfo],
+    ) -> Result<(), ProgramError> {
+        if !admin_state.is_multisig() {
+            SignerAccount::check_is_signer(authority)?;
+            return Self::check_is_authority(admin_state, authority);
+        }
+
+        let approvals: usize = admin_state
+            .multisig_signers()
+            .iter()
+            .filter(|key| {
+                core::iter::once(authority)
+                    .chain(signers.iter())
+                    .any(|account| account.is_signer() && account.key() == *key)
+            })
+            .count();
+
+        if approvals < admin_state.multisig_threshold as usize {
+            return Err(RatingsErrors::NotEnoughSigners.into());
+        }
+
+        Ok(())
+    }
 }
 
 pub struct RatingAccount;
 impl RatingAccount {
     pub fn check_is_valid_rating(
         rating_account: &AccountInfo,
-        user: &AccountInfo,
-        movie_title: &[u8],
+        admin: &Pubkey,
+        user: &Pubkey,
+        item_kind: u8,
+        item_id: &[u8; 32],
     ) -> Result<u8, ProgramError> {
-        let (true_rating_key, bump) =
-            find_program_address(&[user.key().as_ref(), movie_title], &crate::ID);
+        let (true_rating_key, bump) = find_program_address(
+            &[
+                admin.as_ref(),
+                user.as_ref(),
+                &[item_kind],
+                item_id.as_ref(),
+            ],
+            &crate::ID,
+        );
 
         if rating_account.key() != &true_rating_key {
             return Err(RatingsErrors::InvalidRatingAccount.into());
@@ -67,6 +178,278 @@ impl RatingAccount {
     }
 }
 
+pub struct ItemAccount;
+impl ItemAccount {
+    pub fn check_is_valid_item(
+        item_account: &AccountInfo,
+        admin: &Pubkey,
+        item_kind: u8,
+        item_id: &[u8; 32],
+    ) -> Result<u8, ProgramError> {
+        let (true_item_key, bump) = find_program_address(
+            &[b"item", admin.as_ref(), &[item_kind], item_id.as_ref()],
+            &crate::ID,
+        );
+
+        if item_account.key() != &true_item_key {
+            return Err(RatingsErrors::InvalidItemAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn check_is_registered(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Err(RatingsErrors::ItemNotRegistered.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn check_is_empty(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(RatingsErrors::ExpectedEmptyAccount.into())
+        }
+    }
+}
+
+pub struct UserAccount;
+impl UserAccount {
+    pub fn check_is_valid_user(
+        user_account: &AccountInfo,
+        admin: &Pubkey,
+        authority: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (true_user_key, bump) =
+            find_program_address(&[b"user", admin.as_ref(), authority.as_ref()], &crate::ID);
+
+        if user_account.key() != &true_user_key {
+            return Err(RatingsErrors::InvalidUserAccount.into());
+        }
+
+        Ok(bump)
+    }
+}
+
+pub struct VoteAccount;
+impl VoteAccount {
+    pub fn check_is_valid_vote(
+        vote_account: &AccountInfo,
+        rating_account: &AccountInfo,
+        voter: &AccountInfo,
+    ) -> Result<u8, ProgramError> {
+        let (true_vote_key, bump) = find_program_address(
+            &[b"vote", rating_account.key().as_ref(), voter.key().as_ref()],
+            &crate::ID,
+        );
+
+        if vote_account.key() != &true_vote_key {
+            return Err(RatingsErrors::InvalidVoteAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn check_is_empty(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(RatingsErrors::AlreadyVoted.into())
+        }
+    }
+}
+
+pub struct PendingChangeAccount;
+impl PendingChangeAccount {
+    pub fn check_is_valid_pending_change(
+        pending_change_account: &AccountInfo,
+        admin: &AccountInfo,
+    ) -> Result<u8, ProgramError> {
+        let (true_pending_change_key, bump) =
+            find_program_address(&[b"pending_change", admin.key().as_ref()], &crate::ID);
+
+        if pending_change_account.key() != &true_pending_change_key {
+            return Err(RatingsErrors::InvalidPendingChangeAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn check_is_empty(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(RatingsErrors::ExpectedEmptyAccount.into())
+        }
+    }
+}
+
+pub struct ModeratorAccount;
+impl ModeratorAccount {
+    pub fn check_is_valid_moderator(
+        moderator_account: &AccountInfo,
+        admin: &Pubkey,
+        moderator: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (true_moderator_key, bump) = find_program_address(
+            &[b"moderator", admin.as_ref(), moderator.as_ref()],
+            &crate::ID,
+        );
+
+        if moderator_account.key() != &true_moderator_key {
+            return Err(RatingsErrors::InvalidModeratorAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn check_is_empty(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(RatingsErrors::ExpectedEmptyAccount.into())
+        }
+    }
+
+    /// Role check for moderation instructions. Passes if `signer` is the admin authority, or
+    /// if `moderator_account` is a live moderator grant for `signer` under this admin. The
+    /// moderator account is not read when the authority signs, so any key can fill the slot
+    pub fn check_is_admin_or_moderator(
+        admin_state: &AdminState,
+        admin: &AccountInfo,
+        signer: &AccountInfo,
+        moderator_account: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        SignerAccount::check_is_signer(signer)?;
+
+        if AdminAccount::check_is_authority(admin_state, signer).is_ok() {
+            return Ok(());
+        }
+
+        Self::check_is_valid_moderator(moderator_account, admin.key(), signer.key())?;
+        let moderator_data = ModeratorState::load(moderator_account)
+            .map_err(|_| RatingsErrors::InvalidModeratorAccount)?;
+
+        if &moderator_data.admin != admin.key() || &moderator_data.moderator != signer.key() {
+            return Err(RatingsErrors::InvalidModeratorAccount.into());
+        }
+
+        Ok(())
+    }
+}
+
+pub struct BanAccount;
+impl BanAccount {
+    pub fn check_is_valid_ban(
+        ban_account: &AccountInfo,
+        admin: &Pubkey,
+        user: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (true_ban_key, bump) =
+            find_program_address(&[b"ban", admin.as_ref(), user.as_ref()], &crate::ID);
+
+        if ban_account.key() != &true_ban_key {
+            return Err(RatingsErrors::InvalidBanAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    /// The ban address is validated first, so any data there is a live ban
+    pub fn check_is_not_banned(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(RatingsErrors::UserBanned.into())
+        }
+    }
+}
+
+pub struct AllowlistAccount;
+impl AllowlistAccount {
+    pub fn check_is_valid_allowlist(
+        allowlist_account: &AccountInfo,
+        admin: &Pubkey,
+        user: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (true_allowlist_key, bump) =
+            find_program_address(&[b"allowlist", admin.as_ref(), user.as_ref()], &crate::ID);
+
+        if allowlist_account.key() != &true_allowlist_key {
+            return Err(RatingsErrors::InvalidAllowlistAccount.into());
+        }
+
+        Ok(bump)
+    }
+
+    /// Passes when the catalog is open, otherwise `allowlist_account` must be the live
+    /// allowlist entry of `user`
+    pub fn check_is_allowlisted(
+        admin_state: &AdminState,
+        admin: &Pubkey,
+        user: &Pubkey,
+        allowlist_account: Option<&AccountInfo>,
+    ) -> Result<(), ProgramError> {
+        if !admin_state.is_allowlist_only() {
+            return Ok(());
+        }
+
+        let allowlist_account = allowlist_account.ok_or(RatingsErrors::NotAllowlisted)?;
+        Self::check_is_valid_allowlist(allowlist_account, admin, user)?;
+        // A missing or removed entry isn't allowlisted, any other failure is the entry's own
+        if allowlist_account.data_is_empty() {
+            return Err(RatingsErrors::NotAllowlisted.into());
+        }
+        AllowlistState::load(allowlist_account)?;
+
+        Ok(())
+    }
+}
+
+pub struct ItemTitle;
+impl ItemTitle {
+    /// Trims, collapses inner whitespace and lowercases so trivially different spellings
+    /// of a title map to the same item
+    pub fn normalize(item_title: &str) -> String {
+        item_title
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    /// Item ID used as the item and rating PDA seed, sha256 of the normalized title
+    pub fn hash(item_title: &str) -> [u8; 32] {
+        let normalized_title: String = Self::normalize(item_title);
+
+        #[cfg(target_os = "solana")]
+        {
+            let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+            let vals: [&[u8]; 1] = [normalized_title.as_bytes()];
+
+            unsafe {
+                pinocchio::syscalls::sol_sha256(
+                    vals.as_ptr() as *const u8,
+                    vals.len() as u64,
+                    hash.as_mut_ptr() as *mut u8,
+                );
+
+                // SAFETY: The syscall has initialized the bytes.
+                hash.assume_init()
+            }
+        }
+
+        #[cfg(not(target_os = "solana"))]
+        {
+            sha2_const_stable::Sha256::new()
+                .update(normalized_title.as_bytes())
+                .finalize()
+        }
+    }
+}
+
 pub struct SystemProgramAccount;
 impl SystemProgramAccount {
     pub fn check_is_system_program(account: &AccountInfo) -> Result<(), ProgramError> {
@@ -1,9 +1,12 @@
-use crate::{helpers::*, AdminState};
+use crate::{
+    helpers::*, AccountHeader, AccountType, AdminState, RatingDimensions, RatingScale, RewardMode,
+};
 use pinocchio::{
     account_info::{AccountInfo, RefMut},
     instruction::{Seed, Signer},
     msg,
     program_error::ProgramError,
+    pubkey::Pubkey,
     sysvars::{rent::Rent, Sysvar},
     ProgramResult,
 };
@@ -48,20 +51,83 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitAdminAccounts<'a> {
 }
 
 pub struct InitAdminPayload {
+    pub catalog_id: u64,
     pub reward_amount: u64,
+    pub rating_scale: RatingScale,
+    pub rating_dimensions: RatingDimensions,
+    pub reward_mode: RewardMode,
+    pub supply_cap: u64,
 }
 
 impl TryFrom<&[u8]> for InitAdminPayload {
     type Error = ProgramError;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [catalog_id: u64][reward_amount: u64][rating_min: u8][rating_max: u8][rating_step: u8]
+        // [dimension_count: u8][dimension_labels: [[u8; 16]; dimension_count]]
+        // [reward_mode: u8][supply_cap: u64]
+        // The rating scale is optional and defaults to 1-10 in steps of 1, the
+        // dimensions are optional and default to none, the reward mode is optional
+        // and defaults to a pre-minted treasury without a supply cap
+        if data.len() < 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let catalog_id: u64 = u64::from_le_bytes(
+            data[..8]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        );
+        let data: &[u8] = &data[8..];
+
         let payload: u64 = u64::from_le_bytes(
             data[..8]
                 .try_into()
                 .map_err(|_| ProgramError::InvalidAccountData)?,
         );
+
+        let (rating_scale, data): (RatingScale, &[u8]) = match data[8..] {
+            [] => (RatingScale::default(), &[]),
+            [min, max, step, ..] => (RatingScale::new(min, max, step)?, &data[11..]),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        let (rating_dimensions, data): (RatingDimensions, &[u8]) = match data.split_first() {
+            None => (RatingDimensions::default(), &[]),
+            Some((&dimension_count, data)) => {
+                let labels_len: usize = dimension_count as usize * RatingDimensions::LABEL_LEN;
+                if data.len() < labels_len {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let (labels, data) = data.split_at(labels_len);
+
+                let labels: Vec<[u8; 16]> = labels
+                    .chunks_exact(RatingDimensions::LABEL_LEN)
+                    .map(|label| label.try_into().unwrap())
+                    .collect();
+                (RatingDimensions::new(&labels)?, data)
+            }
+        };
+
+        let (reward_mode, supply_cap): (RewardMode, u64) = match data {
+            [] => (RewardMode::Treasury, 0),
+            [reward_mode, supply_cap @ ..] => (
+                RewardMode::try_from(*reward_mode)?,
+                u64::from_le_bytes(
+                    supply_cap
+                        .try_into()
+                        .map_err(|_| ProgramError::InvalidInstructionData)?,
+                ),
+            ),
+        };
+
         Ok(Self {
+            catalog_id,
             reward_amount: payload,
+            rating_scale,
+            rating_dimensions,
+            reward_mode,
+            supply_cap,
         })
     }
 }
@@ -93,8 +159,10 @@ impl<'a> InitAdmin<'a> {
         // Moved validations
         SignerAccount::check_is_signer(accounts.authority)?;
         msg!("Authority account validated");
-        accounts.bump = AdminAccount::check_is_valid_admin(accounts.admin)?;
+        accounts.bump = AdminAccount::check_is_valid_admin(accounts.admin, payload.catalog_id)?;
         msg!("Admin account validated");
+        AdminAccount::check_is_not_closed(accounts.admin)?;
+        msg!("Catalog was never closed");
         AdminAccount::check_is_empty(accounts.admin)?;
         msg!("Admin account is empty");
         SystemProgramAccount::check_is_system_program(accounts.system_program)?;
@@ -110,9 +178,11 @@ impl<'a> InitAdmin<'a> {
         )?;
         msg!("Admin ATA account validated");
         let bump_slice: [u8; 1] = [accounts.bump];
+        let catalog_id_bytes: [u8; 8] = payload.catalog_id.to_le_bytes();
 
-        let seeds: [Seed<'_>; 2] = [
+        let seeds: [Seed<'_>; 3] = [
             Seed::from(b"ratings_admin"),
+            Seed::from(catalog_id_bytes.as_ref()),
             Seed::from(bump_slice.as_ref()),
         ];
         let signer: [Signer<'_, '_>; 1] = [Signer::from(&seeds)];
@@ -162,25 +232,53 @@ impl<'a> InitAdmin<'a> {
         .invoke()?;
         msg!("Admin ATA account created and initialized");
 
-        // Mint (1000 * reward_amount) to admin associated token account
-        MintTo {
-            mint: accounts.ratings_mint,
-            account: accounts.admin_ata,
-            mint_authority: accounts.admin,
-            amount: payload.reward_amount * 1000,
-        }
-        .invoke_signed(&signer)?;
-        msg!("Minted to admin ATA account");
         // Write admin state to admin account
         let admin_state = {
             AdminState {
+                header: AccountHeader::new(AccountType::Admin),
                 authority: *accounts.authority.key(),
+                pending_authority: Pubkey::default(),
                 token_mint: *accounts.ratings_mint.key(),
                 reward_amount: payload.reward_amount,
+                catalog_id: payload.catalog_id,
                 bump: accounts.bump,
+                paused: 0,
+                rating_scale: payload.rating_scale,
+                rating_dimensions: payload.rating_dimensions,
+                multisig_threshold: 0,
+                multisig_count: 0,
+                multisig_signers: [Pubkey::default(); AdminState::MAX_MULTISIG_SIGNERS],
+                timelock_delay: 0,
+                moderation_destination: *accounts.authority.key(),
+                allowlist_only: 0,
+                reward_mode: payload.reward_mode as u8,
+                total_ratings: 0,
+                total_rewards_paid: 0,
+                active_raters: 0,
+                supply_cap: payload.supply_cap,
             }
         };
         msg!("Admin state created");
+
+        // In mint mode InitRating mints each reward, so there is no treasury to fund
+        if payload.reward_mode == RewardMode::Treasury {
+            // Mint (1000 * reward_amount) to admin associated token account
+            let treasury_amount: u64 = payload
+                .reward_amount
+                .checked_mul(1000)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            admin_state.check_supply_cap(0, treasury_amount)?;
+
+            MintTo {
+                mint: accounts.ratings_mint,
+                account: accounts.admin_ata,
+                mint_authority: accounts.admin,
+                amount: treasury_amount,
+            }
+            .invoke_signed(&signer)?;
+            msg!("Minted to admin ATA account");
+        }
+
         let mut admin_data: RefMut<'_, [u8]> = accounts.admin.try_borrow_mut_data()?;
         admin_data[..AdminState::LEN].copy_from_slice(admin_state.as_ref());
         msg!("Admin state written to admin account");
@@ -1,6 +1,7 @@
 use crate::{
-    AdminAccount, AdminState, AssociateTokenProgram, AssociatedTokenAccount, MintAccount,
-    RatingAccount, RatingState, SystemProgramAccount, TokenProgramAccount,
+    AdminAccount, AdminState, AllowlistAccount, AssociateTokenProgram, AssociatedTokenAccount,
+    BanAccount, ItemAccount, ItemKind, ItemState, MintAccount, RatingAccount, RatingDimensions,
+    RatingState, RatingsErrors, SystemProgramAccount, TokenProgramAccount, UserAccount, UserState,
 };
 use pinocchio::instruction::{Seed, Signer};
 use pinocchio::program_error::ProgramError;
@@ -11,13 +12,15 @@ use pinocchio::{
 use pinocchio::{msg, ProgramResult};
 use pinocchio_associated_token_account::instructions::Create;
 use pinocchio_system::instructions::CreateAccount;
-use pinocchio_token::instructions::TransferChecked;
+use pinocchio_token::instructions::{MintTo, TransferChecked};
 use pinocchio_token::state::Mint;
 use pinocchio_token::state::TokenAccount as PinoTokenAccount;
 
 pub struct InitRatingAccounts<'a> {
     pub authority: &'a AccountInfo,
     pub rating: &'a AccountInfo,
+    pub item: &'a AccountInfo,
+    pub user: &'a AccountInfo,
     pub authority_ata: &'a AccountInfo,
     pub admin: &'a AccountInfo,
     pub admin_ata: &'a AccountInfo,
@@ -25,7 +28,10 @@ pub struct InitRatingAccounts<'a> {
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub associated_token_program: &'a AccountInfo,
+    pub ban: &'a AccountInfo,
+    pub allowlist: Option<&'a AccountInfo>, // Only needed while the catalog is allowlist-only
     pub rating_bump: u8,
+    pub user_bump: u8,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitRatingAccounts<'a> {
@@ -33,7 +39,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitRatingAccounts<'a> {
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         msg!("Configuring InitRatingAccounts accounts");
-        let [authority, rating, authority_ata, admin, admin_ata, ratings_mint, system_program, token_program, associated_token_program] =
+        let [authority, rating, item, user, authority_ata, admin, admin_ata, ratings_mint, system_program, token_program, associated_token_program, ban, remaining @ ..] =
             accounts
         else {
             return Err(ProgramError::InvalidArgument);
@@ -42,6 +48,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitRatingAccounts<'a> {
         Ok(Self {
             authority,
             rating,
+            item,
+            user,
             authority_ata,
             admin,
             admin_ata,
@@ -49,14 +57,18 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitRatingAccounts<'a> {
             system_program,
             token_program,
             associated_token_program,
+            ban,
+            allowlist: remaining.first(),
             rating_bump: 0, // Placeholder, will be set in process
+            user_bump: 0,   // Placeholder, will be set in process
         })
     }
 }
 
 pub struct InitRatingPayload {
-    pub movie_title: String,
     pub rating: u8,
+    pub sub_ratings: Vec<u8>,
+    pub review: String,
 }
 
 impl TryFrom<&[u8]> for InitRatingPayload {
@@ -64,21 +76,27 @@ impl TryFrom<&[u8]> for InitRatingPayload {
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         msg!("Parsing InitRatingPayload");
-        if data.len() < 2 {
-            // at least 1 byte title + 1 byte rating
+        // [rating: u8][sub_rating_count: u8][sub_ratings][review (optional, rest of data)]
+        let [rating, sub_rating_count, data @ ..] = data else {
             return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let sub_rating_count = *sub_rating_count as usize;
+        if sub_rating_count > RatingDimensions::MAX || data.len() < sub_rating_count {
+            return Err(RatingsErrors::InvalidSubRatings.into());
         }
+        let (sub_ratings, review_bytes) = data.split_at(sub_rating_count);
 
-        let rating = data[data.len() - 1]; // last byte is rating
-        let title_bytes = &data[..data.len() - 1]; // everything else is title
-        let movie_title = String::from_utf8(title_bytes.to_vec())
+        if review_bytes.len() > RatingState::MAX_REVIEW_LEN {
+            return Err(RatingsErrors::ReviewTooLong.into());
+        }
+        let review = String::from_utf8(review_bytes.to_vec())
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-        // validation...
-
         Ok(Self {
-            movie_title,
-            rating,
+            rating: *rating,
+            sub_ratings: sub_ratings.to_vec(),
+            review,
         })
     }
 }
@@ -107,16 +125,48 @@ impl<'a> InitRating<'a> {
         let payload: &InitRatingPayload = &self.payload;
 
         // Perform validations here
-        AdminAccount::check_is_valid_admin(accounts.admin)?;
+        let admin_data: Ref<'_, AdminState> = AdminState::load(accounts.admin)?;
+        AdminAccount::check_is_valid_admin(accounts.admin, admin_data.catalog_id)?;
         msg!("Admin account validated");
+        admin_data.check_not_paused(AdminState::PAUSE_RATINGS)?;
+        msg!("Ratings are not paused");
+        ItemAccount::check_is_registered(accounts.item)?;
+        let item_data: Ref<'_, ItemState> = ItemState::load(accounts.item)?;
+        let (item_id, item_kind): ([u8; 32], u8) = (item_data.item_id, item_data.item_kind);
+        drop(item_data);
+        ItemAccount::check_is_valid_item(accounts.item, accounts.admin.key(), item_kind, &item_id)?;
+        msg!("Item account validated");
         accounts.rating_bump = RatingAccount::check_is_valid_rating(
             accounts.rating,
-            accounts.authority,
-            payload.movie_title.as_bytes(),
+            accounts.admin.key(),
+            accounts.authority.key(),
+            item_kind,
+            &item_id,
         )?;
         msg!("Rating account validated");
         RatingAccount::check_is_empty(accounts.rating)?;
         msg!("Rating account is empty");
+        accounts.user_bump = UserAccount::check_is_valid_user(
+            accounts.user,
+            accounts.admin.key(),
+            accounts.authority.key(),
+        )?;
+        msg!("User account validated");
+        BanAccount::check_is_valid_ban(
+            accounts.ban,
+            accounts.admin.key(),
+            accounts.authority.key(),
+        )?;
+        msg!("Ban account validated");
+        BanAccount::check_is_not_banned(accounts.ban)?;
+        msg!("Authority is not banned");
+        AllowlistAccount::check_is_allowlisted(
+            &admin_data,
+            accounts.admin.key(),
+            accounts.authority.key(),
+            accounts.allowlist,
+        )?;
+        msg!("Authority may rate in this catalog");
         SystemProgramAccount::check_is_system_program(accounts.system_program)?;
         TokenProgramAccount::check_is_token_program(accounts.token_program)?;
         msg!("Token program validated");
@@ -135,47 +185,101 @@ impl<'a> InitRating<'a> {
         )?;
         msg!("Admin ATA account validated");
 
-        // Load admin state
-        let admin_data: Ref<'_, AdminState> = AdminState::load(accounts.admin)?;
-        msg!("Admin state loaded");
         MintAccount::check_is_mint(accounts.ratings_mint, &admin_data.token_mint)?;
         msg!("Mint account validated");
 
         // Set Rating seeds
         let bump_slice: [u8; 1] = [accounts.rating_bump];
-        let rating_seeds: [Seed<'_>; 3] = [
+        let item_kind_slice: [u8; 1] = [item_kind];
+        let rating_seeds: [Seed<'_>; 5] = [
+            Seed::from(accounts.admin.key().as_ref()),
             Seed::from(accounts.authority.key().as_ref()),
-            Seed::from(payload.movie_title.as_bytes()),
+            Seed::from(&item_kind_slice),
+            Seed::from(&item_id),
             Seed::from(&bump_slice),
         ];
         let rating_signer: [Signer<'_, '_>; 1] = [Signer::from(&rating_seeds)];
 
         let rent: Rent = Rent::get()?;
+        let rating_space: usize = RatingState::space(payload.review.len());
 
         // Init Rating pda
         CreateAccount {
             from: accounts.authority,
             to: accounts.rating,
-            lamports: rent.minimum_balance(RatingState::LEN),
-            space: RatingState::LEN as u64,
+            lamports: rent.minimum_balance(rating_space),
+            space: rating_space as u64,
             owner: &crate::ID,
         }
         .invoke_signed(&rating_signer)?;
         msg!("Rating account created");
 
         // Build and serilaise Rating data
-        let rating_state: RatingState = RatingState::set_inner(
-            payload.movie_title.to_owned(),
+        let timestamp: i64 = clock::Clock::get()?.unix_timestamp;
+        let mut rating_state: RatingState = RatingState::set_inner(
+            item_id,
+            ItemKind::try_from(item_kind)?,
             payload.rating,
+            &admin_data.rating_scale,
             *accounts.authority.key(),
-            clock::Clock::get()?.unix_timestamp,
+            timestamp,
             accounts.rating_bump,
         )?;
 
+        rating_state.set_sub_ratings(
+            &payload.sub_ratings,
+            &admin_data.rating_scale,
+            &admin_data.rating_dimensions,
+        )?;
+
         let mut rating_data: RefMut<'_, [u8]> = accounts.rating.try_borrow_mut_data()?;
         rating_data[..RatingState::LEN].copy_from_slice(rating_state.as_ref());
+        RatingState::write_review(&mut rating_data, payload.review.as_bytes())?;
+        drop(rating_data);
         msg!("Rating data serialized");
 
+        // Add the rating to the item aggregate
+        ItemState::load_mut(accounts.item)?.add_rating(
+            payload.rating,
+            &payload.sub_ratings,
+            &admin_data.rating_scale,
+        )?;
+        msg!("Item aggregate updated");
+
+        // Init User pda on the authority's first rating
+        if accounts.user.data_is_empty() {
+            let user_bump_slice: [u8; 1] = [accounts.user_bump];
+            let user_seeds: [Seed<'_>; 4] = [
+                Seed::from(b"user"),
+                Seed::from(accounts.admin.key().as_ref()),
+                Seed::from(accounts.authority.key().as_ref()),
+                Seed::from(&user_bump_slice),
+            ];
+
+            CreateAccount {
+                from: accounts.authority,
+                to: accounts.user,
+                lamports: rent.minimum_balance(UserState::LEN),
+                space: UserState::LEN as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&[Signer::from(&user_seeds)])?;
+            msg!("User account created");
+
+            let user_state: UserState =
+                UserState::set_inner(*accounts.authority.key(), accounts.user_bump)?;
+            let mut user_data: RefMut<'_, [u8]> = accounts.user.try_borrow_mut_data()?;
+            user_data[..UserState::LEN].copy_from_slice(user_state.as_ref());
+            msg!("User data serialized");
+        }
+
+        // Record the rating and its reward on the user profile
+        let mut user_data: RefMut<'_, UserState> = UserState::load_mut(accounts.user)?;
+        user_data.record_rating(timestamp, admin_data.reward_amount)?;
+        let new_rater: bool = user_data.add_active_rating()?;
+        drop(user_data);
+        msg!("User profile updated");
+
         // Init Authority ATA if it doesn't exist
         if accounts.authority_ata.data_len() != PinoTokenAccount::LEN {
             Create {
@@ -190,21 +294,48 @@ impl<'a> InitRating<'a> {
             msg!("Authority ATA created");
         }
 
-        // Transfer tokens from admin to authority
-        let mint_data: Ref<'_, Mint> = Mint::from_account_info(accounts.ratings_mint)?;
-        TransferChecked {
-            from: accounts.admin_ata,
-            mint: accounts.ratings_mint,
-            to: accounts.authority_ata,
-            authority: accounts.admin,
-            amount: admin_data.reward_amount,
-            decimals: mint_data.decimals(),
-        }
-        .invoke_signed(&[Signer::from(&[
+        let catalog_id_bytes: [u8; 8] = admin_data.catalog_id.to_le_bytes();
+        let admin_bump_slice: [u8; 1] = [admin_data.bump];
+        let admin_seeds: [Seed<'_>; 3] = [
             Seed::from(b"ratings_admin"),
-            Seed::from(&[admin_data.bump]),
-        ])])?;
-        msg!("Tokens transferred from admin to authority");
+            Seed::from(&catalog_id_bytes),
+            Seed::from(&admin_bump_slice),
+        ];
+
+        if admin_data.is_mint_rewards() {
+            // Mint the reward straight to the authority, within the supply cap
+            let supply: u64 = Mint::from_account_info(accounts.ratings_mint)?.supply();
+            admin_data.check_supply_cap(supply, admin_data.reward_amount)?;
+            msg!("Reward is within the supply cap");
+
+            MintTo {
+                mint: accounts.ratings_mint,
+                account: accounts.authority_ata,
+                mint_authority: accounts.admin,
+                amount: admin_data.reward_amount,
+            }
+            .invoke_signed(&[Signer::from(&admin_seeds)])?;
+            msg!("Tokens minted to authority");
+        } else {
+            // Transfer tokens from admin to authority
+            let mint_data: Ref<'_, Mint> = Mint::from_account_info(accounts.ratings_mint)?;
+            TransferChecked {
+                from: accounts.admin_ata,
+                mint: accounts.ratings_mint,
+                to: accounts.authority_ata,
+                authority: accounts.admin,
+                amount: admin_data.reward_amount,
+                decimals: mint_data.decimals(),
+            }
+            .invoke_signed(&[Signer::from(&admin_seeds)])?;
+            msg!("Tokens transferred from admin to authority");
+        }
+
+        // The admin signed the reward above, so its counters are only borrowed mutably now
+        let reward_amount: u64 = admin_data.reward_amount;
+        drop(admin_data);
+        AdminState::load_mut(accounts.admin)?.record_rating(reward_amount, new_rater)?;
+        msg!("Admin counters updated");
         Ok(())
     }
 }
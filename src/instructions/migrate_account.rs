@@ -0,0 +1,286 @@
+use crate::{
+    AccountHeader, AccountType, AdminAccount, AdminState, AllowlistAccount, AllowlistState,
+    BanAccount, BanState, ClosedAdminState, ItemAccount, ItemState, ModeratorAccount,
+    ModeratorState, PendingChangeState, RatingAccount, RatingState, RatingsErrors, SignerAccount,
+    SystemProgramAccount, UserAccount, UserState, VoteState, ACCOUNT_VERSION, TRAILING_LEN_PREFIX,
+    TYPED_VERSION, UNTYPED_VERSION,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    msg,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+pub struct MigrateAccountAccounts<'a> {
+    pub account: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub admin: &'a AccountInfo, // Only a PDA seed, for rating, item and user accounts
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccountAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [account, payer, system_program, admin] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            account,
+            payer,
+            system_program,
+            admin,
+        })
+    }
+}
+
+pub struct MigrateAccountPayload {
+    pub account_type: AccountType,
+}
+
+impl TryFrom<&[u8]> for MigrateAccountPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [account_type: u8]
+        let [account_type] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self {
+            account_type: AccountType::try_from(*account_type)?,
+        })
+    }
+}
+
+pub struct MigrateAccount<'a> {
+    pub accounts: MigrateAccountAccounts<'a>,
+    pub payload: MigrateAccountPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for MigrateAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: MigrateAccountAccounts<'_> = MigrateAccountAccounts::try_from(accounts)?;
+        let payload: MigrateAccountPayload = MigrateAccountPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> MigrateAccount<'a> {
+    pub const DISCRIMINATOR: u8 = 25;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.payer)?;
+        msg!("Checked if payer is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        if self.accounts.account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let (version, growth): (u8, usize) = self.version()?;
+        msg!("Account needs migration");
+
+        let new_len: usize = self.accounts.account.data_len() + growth;
+
+        let required_lamports: u64 = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(self.accounts.account.lamports());
+        if required_lamports > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.account,
+                lamports: required_lamports,
+            }
+            .invoke()?;
+            msg!("Account rent topped up");
+        }
+
+        AccountHeader::upgrade(
+            self.accounts.account,
+            version,
+            new_len,
+            self.payload.account_type,
+        )?;
+        msg!("Account upgraded");
+
+        // An older account of another type with a matching size would be upgraded here too,
+        // re-deriving the PDA from the upgraded fields rejects it
+        self.check_is_valid_pda()?;
+        msg!("Account address validated");
+
+        Ok(())
+    }
+
+    /// Fixed length of the current layout of `account_type`, of its layout with a version 2 or 3
+    /// header, and whether a trailing field follows the fixed state
+    const fn layout(account_type: AccountType) -> (usize, usize, bool) {
+        match account_type {
+            AccountType::Admin => (AdminState::LEN, AdminState::PRE_SUPPLY_CAP_LEN, false),
+            AccountType::Rating => (RatingState::LEN, RatingState::LEN, true),
+            AccountType::Item => (ItemState::LEN, ItemState::LEN, true),
+            AccountType::User => (UserState::LEN, UserState::LEN, false),
+            AccountType::Vote => (VoteState::LEN, VoteState::LEN, false),
+            AccountType::PendingChange => (
+                PendingChangeState::LEN,
+                PendingChangeState::PRE_SUPPLY_CAP_LEN,
+                false,
+            ),
+            AccountType::Moderator => (ModeratorState::LEN, ModeratorState::LEN, false),
+            AccountType::Ban => (BanState::LEN, BanState::LEN, false),
+            AccountType::Allowlist => (AllowlistState::LEN, AllowlistState::PRE_PAYER_LEN, false),
+            AccountType::ClosedAdmin => (ClosedAdminState::LEN, ClosedAdminState::LEN, false),
+        }
+    }
+
+    /// Returns the account's version and how many bytes the migration adds. Version 1 layouts
+    /// have no header, version 2 ones have the header but no account type, and version 3 admins,
+    /// pending changes and allowlist entries miss the fields appended in version 4
+    fn version(&self) -> Result<(u8, usize), ProgramError> {
+        let data: Ref<'_, [u8]> = self.accounts.account.try_borrow_data()?;
+        let account_type: AccountType = self.payload.account_type;
+
+        // Trailing fields are [u16 LE length][bytes] right after the fixed state
+        let fits = |len: usize, trailing: bool| -> bool {
+            if !trailing {
+                return data.len() == len;
+            }
+
+            data.len() >= len + TRAILING_LEN_PREFIX
+                && data.len()
+                    == len
+                        + TRAILING_LEN_PREFIX
+                        + u16::from_le_bytes([data[len], data[len + 1]]) as usize
+        };
+
+        // A current header is only trusted once the size fits the layout of the type it names
+        if let Some(&[version, kind]) = data.get(..2) {
+            if let Ok(kind) = AccountType::try_from(kind) {
+                let (len, _, trailing) = Self::layout(kind);
+                if (kind.layout_version()..=ACCOUNT_VERSION).contains(&version)
+                    && fits(len, trailing)
+                {
+                    if kind != account_type {
+                        return Err(RatingsErrors::InvalidAccountType.into());
+                    }
+                    return Err(RatingsErrors::AccountAlreadyMigrated.into());
+                }
+            }
+        }
+
+        // Closed admins are created at the current version and have nothing to migrate
+        if account_type == AccountType::ClosedAdmin {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (len, legacy_len, trailing): (usize, usize, bool) = Self::layout(account_type);
+
+        // The header version tells older layouts apart, a typed one must also be of this type
+        let header_version: Option<u8> = match data.get(..2) {
+            Some(&[UNTYPED_VERSION, _]) => Some(UNTYPED_VERSION),
+            Some(&[version, kind])
+                if (TYPED_VERSION..account_type.layout_version()).contains(&version)
+                    && kind == account_type as u8 =>
+            {
+                Some(version)
+            }
+            _ => None,
+        };
+
+        // Admins from before the counters are a shorter version 1 and go through MigrateAdmin
+        match header_version {
+            Some(version) if fits(legacy_len, trailing) => Ok((version, len - legacy_len)),
+            _ if fits(legacy_len - AccountHeader::LEN, trailing) => {
+                Ok((1, len - legacy_len + AccountHeader::LEN))
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn check_is_valid_pda(&self) -> ProgramResult {
+        let account: &AccountInfo = self.accounts.account;
+        let admin: &AccountInfo = self.accounts.admin;
+
+        match self.payload.account_type {
+            AccountType::Admin => {
+                let admin_data: Ref<'_, AdminState> = AdminState::load(account)?;
+                AdminAccount::check_is_valid_admin_with_bump(account, &admin_data)?;
+            }
+            AccountType::Rating => {
+                let rating_data: Ref<'_, RatingState> = RatingState::load(account)?;
+                RatingAccount::check_is_valid_rating(
+                    account,
+                    admin.key(),
+                    &rating_data.owner,
+                    rating_data.item_kind,
+                    &rating_data.item_id,
+                )?;
+            }
+            AccountType::Item => {
+                let item_data: Ref<'_, ItemState> = ItemState::load(account)?;
+                ItemAccount::check_is_valid_item(
+                    account,
+                    admin.key(),
+                    item_data.item_kind,
+                    &item_data.item_id,
+                )?;
+            }
+            AccountType::User => {
+                let user_data: Ref<'_, UserState> = UserState::load(account)?;
+                UserAccount::check_is_valid_user(account, admin.key(), &user_data.authority)?;
+            }
+            AccountType::Vote => {
+                let vote_data: Ref<'_, VoteState> = VoteState::load(account)?;
+                let (true_vote_key, _) = find_program_address(
+                    &[b"vote", vote_data.rating.as_ref(), vote_data.voter.as_ref()],
+                    &crate::ID,
+                );
+                if account.key() != &true_vote_key {
+                    return Err(RatingsErrors::InvalidVoteAccount.into());
+                }
+            }
+            AccountType::PendingChange => {
+                let pending_change_data: Ref<'_, PendingChangeState> =
+                    PendingChangeState::load(account)?;
+                let (true_pending_change_key, _) = find_program_address(
+                    &[b"pending_change", pending_change_data.admin.as_ref()],
+                    &crate::ID,
+                );
+                if account.key() != &true_pending_change_key {
+                    return Err(RatingsErrors::InvalidPendingChangeAccount.into());
+                }
+            }
+            AccountType::Moderator => {
+                let moderator_data: Ref<'_, ModeratorState> = ModeratorState::load(account)?;
+                ModeratorAccount::check_is_valid_moderator(
+                    account,
+                    &moderator_data.admin,
+                    &moderator_data.moderator,
+                )?;
+            }
+            AccountType::Ban => {
+                let ban_data: Ref<'_, BanState> = BanState::load(account)?;
+                BanAccount::check_is_valid_ban(account, &ban_data.admin, &ban_data.user)?;
+            }
+            AccountType::Allowlist => {
+                let allowlist_data: Ref<'_, AllowlistState> = AllowlistState::load(account)?;
+                AllowlistAccount::check_is_valid_allowlist(
+                    account,
+                    &allowlist_data.admin,
+                    &allowlist_data.user,
+                )?;
+            }
+            AccountType::ClosedAdmin => return Err(ProgramError::InvalidAccountData),
+        }
+
+        Ok(())
+    }
+}
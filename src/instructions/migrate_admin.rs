@@ -0,0 +1,145 @@
+use crate::{
+    AccountHeader, AccountType, AdminAccount, AdminState, RatingsErrors, SignerAccount,
+    SystemProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+pub struct MigrateAdminAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAdminAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, payer, system_program, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            payer,
+            system_program,
+            signers,
+        })
+    }
+}
+
+pub struct MigrateAdminPayload {
+    pub total_ratings: u64,
+    pub total_rewards_paid: u64,
+    pub active_raters: u64,
+}
+
+impl TryFrom<&[u8]> for MigrateAdminPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [total_ratings: u64][total_rewards_paid: u64][active_raters: u64]
+        // The counters can't be rebuilt on-chain, so the authority backfills them from an indexer
+        if data.len() != 24 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let read_u64 = |offset: usize| -> Result<u64, ProgramError> {
+            Ok(u64::from_le_bytes(
+                data[offset..offset + 8]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            ))
+        };
+
+        Ok(Self {
+            total_ratings: read_u64(0)?,
+            total_rewards_paid: read_u64(8)?,
+            active_raters: read_u64(16)?,
+        })
+    }
+}
+
+pub struct MigrateAdmin<'a> {
+    pub accounts: MigrateAdminAccounts<'a>,
+    pub payload: MigrateAdminPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for MigrateAdmin<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: MigrateAdminAccounts<'_> = MigrateAdminAccounts::try_from(accounts)?;
+        let payload: MigrateAdminPayload = MigrateAdminPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> MigrateAdmin<'a> {
+    pub const DISCRIMINATOR: u8 = 24;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.payer)?;
+        msg!("Checked if payer is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        if self.accounts.admin.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        match self.accounts.admin.data_len() {
+            AdminState::PRE_COUNTERS_LEN => {}
+            // Their counters can't be overwritten through this instruction
+            AdminState::LEN => return Err(RatingsErrors::AccountAlreadyMigrated.into()),
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+        msg!("Admin account needs migration");
+
+        // The payer covers the rent for the extra bytes
+        let required_lamports: u64 = Rent::get()?
+            .minimum_balance(AdminState::LEN)
+            .saturating_sub(self.accounts.admin.lamports());
+        if required_lamports > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.admin,
+                lamports: required_lamports,
+            }
+            .invoke()?;
+            msg!("Admin rent topped up");
+        }
+
+        // Admins from before the counters are version 1, this adds the header, zeroed counters
+        // and no supply cap. The checks below fail the whole migration if the signers are wrong
+        AccountHeader::upgrade(self.accounts.admin, 1, AdminState::LEN, AccountType::Admin)?;
+        msg!("Admin account upgraded");
+
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        admin_data.set_counters(
+            self.payload.total_ratings,
+            self.payload.total_rewards_paid,
+            self.payload.active_raters,
+        )?;
+        msg!("Admin counters backfilled");
+
+        Ok(())
+    }
+}
@@ -7,5 +7,74 @@ pub use init_admin::*;
 pub mod init_rating;
 pub use init_rating::*;
 
+pub mod update_rating;
+pub use update_rating::*;
+
+pub mod update_review;
+pub use update_review::*;
+
+pub mod register_item;
+pub use register_item::*;
+
+pub mod vote_on_rating;
+pub use vote_on_rating::*;
+
+pub mod update_admin_config;
+pub use update_admin_config::*;
+
+pub mod propose_authority;
+pub use propose_authority::*;
+
+pub mod accept_authority;
+pub use accept_authority::*;
+
+pub mod cancel_authority_transfer;
+pub use cancel_authority_transfer::*;
+
+pub mod set_pause;
+pub use set_pause::*;
+
+pub mod set_multisig;
+pub use set_multisig::*;
+
+pub mod queue_admin_change;
+pub use queue_admin_change::*;
+
+pub mod execute_admin_change;
+pub use execute_admin_change::*;
+
+pub mod cancel_admin_change;
+pub use cancel_admin_change::*;
+
+pub mod grant_moderator;
+pub use grant_moderator::*;
+
+pub mod revoke_moderator;
+pub use revoke_moderator::*;
+
+pub mod moderate_rating;
+pub use moderate_rating::*;
+
+pub mod ban_user;
+pub use ban_user::*;
+
+pub mod unban_user;
+pub use unban_user::*;
+
+pub mod add_to_allowlist;
+pub use add_to_allowlist::*;
+
+pub mod remove_from_allowlist;
+pub use remove_from_allowlist::*;
+
+pub mod close_admin;
+pub use close_admin::*;
+
+pub mod migrate_admin;
+pub use migrate_admin::*;
+
+pub mod migrate_account;
+pub use migrate_account::*;
+
 pub mod helpers;
 pub use helpers::*;
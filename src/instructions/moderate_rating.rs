@@ -0,0 +1,199 @@
+use crate::{
+    AdminAccount, AdminState, ItemAccount, ItemState, ModeratorAccount, RatingAccount, RatingState,
+    RatingsErrors, UserAccount, UserState,
+};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    log::sol_log_data,
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+/// What a moderator does with a rating, stored as the first payload byte
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationAction {
+    Hide = 0,   // Keep the account but mark it hidden
+    Remove = 1, // Strip the review and send the freed rent to the moderation destination
+}
+
+impl TryFrom<u8> for ModerationAction {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Hide),
+            1 => Ok(Self::Remove),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct ModerateRatingAccounts<'a> {
+    pub moderator: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub moderator_account: &'a AccountInfo,
+    pub rating: &'a AccountInfo,
+    pub item: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub user: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ModerateRatingAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [moderator, admin, moderator_account, rating, item, destination, user] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            moderator,
+            admin,
+            moderator_account,
+            rating,
+            item,
+            destination,
+            user,
+        })
+    }
+}
+
+pub struct ModerateRatingPayload {
+    pub action: ModerationAction,
+    pub reason: u8,
+}
+
+impl TryFrom<&[u8]> for ModerateRatingPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [action: u8][reason: u8]
+        let [action, reason] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self {
+            action: ModerationAction::try_from(*action)?,
+            reason: *reason,
+        })
+    }
+}
+
+pub struct ModerateRating<'a> {
+    pub accounts: ModerateRatingAccounts<'a>,
+    pub payload: ModerateRatingPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for ModerateRating<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: ModerateRatingAccounts<'_> = ModerateRatingAccounts::try_from(accounts)?;
+        let payload: ModerateRatingPayload = ModerateRatingPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> ModerateRating<'a> {
+    pub const DISCRIMINATOR: u8 = 18;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        ModeratorAccount::check_is_admin_or_moderator(
+            &admin_data,
+            self.accounts.admin,
+            self.accounts.moderator,
+            self.accounts.moderator_account,
+        )?;
+        msg!("Moderator validated");
+
+        let mut rating_data: RefMut<'_, RatingState> = RatingState::load_mut(self.accounts.rating)?;
+        msg!("Loaded rating data");
+
+        RatingAccount::check_is_valid_rating(
+            self.accounts.rating,
+            self.accounts.admin.key(),
+            &rating_data.owner,
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated rating account");
+        ItemAccount::check_is_valid_item(
+            self.accounts.item,
+            self.accounts.admin.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated item account");
+        UserAccount::check_is_valid_user(
+            self.accounts.user,
+            self.accounts.admin.key(),
+            &rating_data.owner,
+        )?;
+        msg!("Validated user account");
+
+        // Hidden ratings already left the aggregate and counters, so only a visible one is
+        // taken out here
+        let was_hidden: bool = rating_data.is_hidden();
+        if !was_hidden {
+            ItemState::load_mut(self.accounts.item)?.remove_rating(
+                rating_data.rating,
+                rating_data.sub_ratings(),
+                &admin_data.rating_scale,
+            )?;
+            msg!("Removed rating from item aggregate");
+
+            let rater_left: bool = !self.accounts.user.data_is_empty()
+                && UserState::load_mut(self.accounts.user)?.remove_active_rating()?;
+            admin_data.remove_rating(rater_left)?;
+            msg!("Admin counters updated");
+        }
+
+        match self.payload.action {
+            ModerationAction::Hide => {
+                rating_data.hide(self.payload.reason)?;
+                msg!("Rating hidden");
+            }
+            ModerationAction::Remove => {
+                if self.accounts.destination.key() != &admin_data.moderation_destination {
+                    return Err(RatingsErrors::InvalidModerationDestination.into());
+                }
+                rating_data.remove(self.payload.reason)?;
+                drop(rating_data);
+
+                // The account stays behind so its owner can't delete it and rate the item again
+                // for another reward, only the review and the rent it held go
+                let space: usize = RatingState::space(0);
+                self.accounts.rating.resize(space)?;
+                RatingState::write_review(&mut self.accounts.rating.try_borrow_mut_data()?, &[])?;
+                msg!("Rating removed");
+
+                let freed_lamports: u64 = self
+                    .accounts
+                    .rating
+                    .lamports()
+                    .saturating_sub(Rent::get()?.minimum_balance(space));
+
+                *self.accounts.rating.try_borrow_mut_lamports()? -= freed_lamports;
+                *self.accounts.destination.try_borrow_mut_lamports()? += freed_lamports;
+                msg!("Transferred lamports to moderation destination");
+            }
+        }
+
+        // Emitted for both actions so indexers see moderation without refetching ratings
+        sol_log_data(&[
+            b"moderate_rating",
+            self.accounts.rating.key().as_ref(),
+            &[self.payload.action as u8, self.payload.reason],
+        ]);
+
+        Ok(())
+    }
+}
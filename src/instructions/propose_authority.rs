@@ -0,0 +1,78 @@
+use crate::{AdminAccount, AdminState, RatingsErrors};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct ProposeAuthorityAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub new_authority: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, new_authority, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            new_authority,
+            signers,
+        })
+    }
+}
+
+pub struct ProposeAuthority<'a> {
+    pub accounts: ProposeAuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ProposeAuthorityAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ProposeAuthority<'a> {
+    pub const DISCRIMINATOR: u8 = 8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        if admin_data.is_timelocked() {
+            return Err(RatingsErrors::TimelockActive.into());
+        }
+
+        // The new key only has to sign when accepting, so proposing never locks anyone out
+        if self.accounts.new_authority.key() == &Pubkey::default()
+            || self.accounts.new_authority.key() == &admin_data.authority
+        {
+            return Err(RatingsErrors::InvalidPendingAuthority.into());
+        }
+
+        admin_data.set_pending_authority(*self.accounts.new_authority.key())?;
+        msg!("Authority transfer proposed");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    AdminAccount, AdminState, PendingChangeAccount, PendingChangeState, RatingsErrors,
+    SignerAccount, SystemProgramAccount, UpdateAdminConfigPayload,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct QueueAdminChangeAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub pending_change: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for QueueAdminChangeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, pending_change, payer, system_program, signers @ ..] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            pending_change,
+            payer,
+            system_program,
+            signers,
+        })
+    }
+}
+
+pub struct QueueAdminChange<'a> {
+    pub accounts: QueueAdminChangeAccounts<'a>,
+    pub payload: UpdateAdminConfigPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for QueueAdminChange<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: QueueAdminChangeAccounts<'_> = QueueAdminChangeAccounts::try_from(accounts)?;
+        let payload: UpdateAdminConfigPayload = UpdateAdminConfigPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> QueueAdminChange<'a> {
+    pub const DISCRIMINATOR: u8 = 13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.payer)?;
+        msg!("Checked if payer is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        let pending_change_bump: u8 = PendingChangeAccount::check_is_valid_pending_change(
+            self.accounts.pending_change,
+            self.accounts.admin,
+        )?;
+        msg!("Pending change account validated");
+        PendingChangeAccount::check_is_empty(self.accounts.pending_change)?;
+        msg!("No change is already queued");
+
+        let new_authority: Pubkey = match self.payload.new_authority {
+            Some(new_authority) => {
+                if new_authority == Pubkey::default() || new_authority == admin_data.authority {
+                    return Err(RatingsErrors::InvalidPendingAuthority.into());
+                }
+                new_authority
+            }
+            None => Pubkey::default(),
+        };
+
+        let mut field_mask: u8 = 0;
+        if self.payload.reward_amount.is_some() {
+            field_mask |= UpdateAdminConfigPayload::REWARD_AMOUNT;
+        }
+        if self.payload.timelock_delay.is_some() {
+            field_mask |= UpdateAdminConfigPayload::TIMELOCK_DELAY;
+        }
+        if self.payload.new_authority.is_some() {
+            field_mask |= UpdateAdminConfigPayload::AUTHORITY;
+        }
+        if let Some(moderation_destination) = self.payload.moderation_destination {
+            if moderation_destination == Pubkey::default() {
+                return Err(RatingsErrors::InvalidModerationDestination.into());
+            }
+            field_mask |= UpdateAdminConfigPayload::MODERATION_DESTINATION;
+        }
+        if self.payload.allowlist_only.is_some() {
+            field_mask |= UpdateAdminConfigPayload::ALLOWLIST_ONLY;
+        }
+        if self.payload.reward_mode.is_some() {
+            field_mask |= UpdateAdminConfigPayload::REWARD_MODE;
+        }
+
+        let eta: i64 = Clock::get()?
+            .unix_timestamp
+            .checked_add(admin_data.timelock_delay)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        drop(admin_data);
+
+        // Build the pending change up front so a change that could never apply is never queued
+        let mut pending_change_state: PendingChangeState = PendingChangeState::set_inner(
+            *self.accounts.admin.key(),
+            *self.accounts.payer.key(),
+            eta,
+            pending_change_bump,
+        )?;
+        pending_change_state.set_change(
+            field_mask,
+            self.payload.reward_amount.unwrap_or_default(),
+            self.payload.timelock_delay.unwrap_or_default(),
+            new_authority,
+            self.payload.moderation_destination.unwrap_or_default(),
+        )?;
+        pending_change_state.set_allowlist_only(self.payload.allowlist_only.unwrap_or_default())?;
+        if let Some((reward_mode, supply_cap)) = self.payload.reward_mode {
+            pending_change_state.set_reward_mode(reward_mode, supply_cap)?;
+        }
+
+        let bump_slice: [u8; 1] = [pending_change_bump];
+        let pending_change_seeds: [Seed<'_>; 3] = [
+            Seed::from(b"pending_change"),
+            Seed::from(self.accounts.admin.key().as_ref()),
+            Seed::from(&bump_slice),
+        ];
+
+        CreateAccount {
+            from: self.accounts.payer,
+            to: self.accounts.pending_change,
+            lamports: Rent::get()?.minimum_balance(PendingChangeState::LEN),
+            space: PendingChangeState::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&pending_change_seeds)])?;
+        msg!("Pending change account created");
+
+        let mut pending_change_data: RefMut<'_, [u8]> =
+            self.accounts.pending_change.try_borrow_mut_data()?;
+        pending_change_data.copy_from_slice(pending_change_state.as_ref());
+        msg!("Admin change queued");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{helpers::*, AdminState, ItemKind, ItemState, RatingsErrors};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct RegisterItemAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub item: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub item_bump: u8,
+    pub item_id: [u8; 32],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RegisterItemAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, item, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            item,
+            system_program,
+            item_bump: 0,       // Placeholder, will be set in process
+            item_id: [0u8; 32], // Placeholder, will be set in process
+        })
+    }
+}
+
+pub struct RegisterItemPayload {
+    pub item_kind: ItemKind,
+    pub release_year: u16,
+    pub external_id: [u8; 16],
+    pub item_title: String,
+}
+
+impl TryFrom<&[u8]> for RegisterItemPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [item_kind: u8][release_year: u16][external_id: [u8; 16]][title (rest of data)]
+        let [item_kind, data @ ..] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let item_kind = ItemKind::try_from(*item_kind)?;
+
+        if data.len() < 2 + 16 + 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let release_year = u16::from_le_bytes(
+            data[..2]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        );
+        let external_id: [u8; 16] = data[2..18]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        if data.len() - 18 > ItemState::MAX_TITLE_LEN {
+            return Err(RatingsErrors::ItemTitleTooLong.into());
+        }
+        let item_title = String::from_utf8(data[18..].to_vec())
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        if ItemTitle::normalize(&item_title).is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            item_kind,
+            release_year,
+            external_id,
+            item_title,
+        })
+    }
+}
+
+pub struct RegisterItem<'a> {
+    pub accounts: RegisterItemAccounts<'a>,
+    pub payload: RegisterItemPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for RegisterItem<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: RegisterItemAccounts<'_> = RegisterItemAccounts::try_from(accounts)?;
+        let payload: RegisterItemPayload = RegisterItemPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> RegisterItem<'a> {
+    pub const DISCRIMINATOR: u8 = 5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts: &mut RegisterItemAccounts<'_> = &mut self.accounts;
+        let payload: &RegisterItemPayload = &self.payload;
+
+        SignerAccount::check_is_signer(accounts.authority)?;
+        msg!("Authority account validated");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(accounts.admin)?;
+        AdminAccount::check_is_valid_admin(accounts.admin, admin_data.catalog_id)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_authority(&admin_data, accounts.authority)?;
+        drop(admin_data);
+        msg!("Admin authority validated");
+        accounts.item_id = ItemTitle::hash(&payload.item_title);
+        accounts.item_bump = ItemAccount::check_is_valid_item(
+            accounts.item,
+            accounts.admin.key(),
+            payload.item_kind as u8,
+            &accounts.item_id,
+        )?;
+        msg!("Item account validated");
+        ItemAccount::check_is_empty(accounts.item)?;
+        msg!("Item account is empty");
+        SystemProgramAccount::check_is_system_program(accounts.system_program)?;
+        msg!("System program validated");
+
+        // Build the item state up front so bad metadata fails before any lamports move
+        let item_space: usize = ItemState::space(payload.item_title.len());
+        let item_state: ItemState = ItemState::set_inner(
+            accounts.item_id,
+            payload.external_id,
+            payload.item_kind,
+            payload.release_year,
+            accounts.item_bump,
+        )?;
+
+        let bump_slice: [u8; 1] = [accounts.item_bump];
+        let item_kind_slice: [u8; 1] = [payload.item_kind as u8];
+        let item_seeds: [Seed<'_>; 5] = [
+            Seed::from(b"item"),
+            Seed::from(accounts.admin.key().as_ref()),
+            Seed::from(&item_kind_slice),
+            Seed::from(&accounts.item_id),
+            Seed::from(&bump_slice),
+        ];
+
+        CreateAccount {
+            from: accounts.authority,
+            to: accounts.item,
+            lamports: Rent::get()?.minimum_balance(item_space),
+            space: item_space as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&item_seeds)])?;
+        msg!("Item account created");
+
+        let mut item_data: RefMut<'_, [u8]> = accounts.item.try_borrow_mut_data()?;
+        item_data[..ItemState::LEN].copy_from_slice(item_state.as_ref());
+        ItemState::write_title(&mut item_data, payload.item_title.as_bytes())?;
+        msg!("Item registered");
+
+        Ok(())
+    }
+}
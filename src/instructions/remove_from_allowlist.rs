@@ -0,0 +1,137 @@
+use crate::{AdminAccount, AdminState, AllowlistAccount, AllowlistState};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct RemoveFromAllowlistAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub allowlist_entries: &'a [AccountInfo], // [allowlist entry, payer] pairs
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], usize)> for RemoveFromAllowlistAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], usize)) -> Result<Self, Self::Error> {
+        // The first `entry_count` pairs of trailing accounts are the entries and their payers,
+        // any after them are signers
+        let (accounts, entry_count) = input;
+        let [authority, admin, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let Some((allowlist_entries, signers)) = remaining.split_at_checked(entry_count * 2) else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            allowlist_entries,
+            signers,
+        })
+    }
+}
+
+pub struct RemoveFromAllowlistPayload {
+    pub entry_count: usize,
+}
+
+impl TryFrom<&[u8]> for RemoveFromAllowlistPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [entry_count: u8]
+        let [entry_count] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        if *entry_count == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            entry_count: *entry_count as usize,
+        })
+    }
+}
+
+pub struct RemoveFromAllowlist<'a> {
+    pub accounts: RemoveFromAllowlistAccounts<'a>,
+    pub payload: RemoveFromAllowlistPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for RemoveFromAllowlist<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let payload: RemoveFromAllowlistPayload = RemoveFromAllowlistPayload::try_from(data)?;
+        let accounts: RemoveFromAllowlistAccounts<'_> =
+            RemoveFromAllowlistAccounts::try_from((accounts, payload.entry_count))?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> RemoveFromAllowlist<'a> {
+    pub const DISCRIMINATOR: u8 = 22;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Entries migrated from before the payer was stored refund the authority instead. The
+        // last authority of a closed catalog can still remove its entries
+        let authority: Pubkey = if AdminAccount::is_closed(self.accounts.admin)? {
+            AdminAccount::check_is_closed_authority(self.accounts.admin, self.accounts.authority)?;
+            msg!("Closed admin authority validated");
+            *self.accounts.authority.key()
+        } else {
+            let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+            AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+            msg!("Admin account validated");
+            AdminAccount::check_is_privileged(
+                &admin_data,
+                self.accounts.authority,
+                self.accounts.signers,
+            )?;
+            msg!("Admin authority validated");
+            admin_data.authority
+        };
+
+        for entry in self.accounts.allowlist_entries.chunks_exact(2) {
+            let [allowlist_entry, payer] = entry else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let allowlist_data: Ref<'_, AllowlistState> = AllowlistState::load(allowlist_entry)?;
+            AllowlistAccount::check_is_valid_allowlist(
+                allowlist_entry,
+                self.accounts.admin.key(),
+                &allowlist_data.user,
+            )?;
+
+            let refund_to: &Pubkey = if allowlist_data.payer == Pubkey::default() {
+                &authority
+            } else {
+                &allowlist_data.payer
+            };
+            if refund_to != payer.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            drop(allowlist_data);
+
+            let allowlist_lamports: u64 = allowlist_entry.lamports();
+
+            *allowlist_entry.try_borrow_mut_lamports()? -= allowlist_lamports;
+            *payer.try_borrow_mut_lamports()? += allowlist_lamports;
+
+            allowlist_entry.close()?;
+            msg!("User removed from allowlist");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{AdminAccount, AdminState, ModeratorAccount, ModeratorState};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+pub struct RevokeModeratorAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub moderator_account: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeModeratorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, moderator_account, destination, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            moderator_account,
+            destination,
+            signers,
+        })
+    }
+}
+
+pub struct RevokeModerator<'a> {
+    pub accounts: RevokeModeratorAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeModerator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: RevokeModeratorAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> RevokeModerator<'a> {
+    pub const DISCRIMINATOR: u8 = 17;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Moderators of a closed catalog can still be revoked for their rent
+        if AdminAccount::is_closed(self.accounts.admin)? {
+            AdminAccount::check_is_closed_authority(self.accounts.admin, self.accounts.authority)?;
+            msg!("Closed admin authority validated");
+        } else {
+            let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+            AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+            msg!("Admin account validated");
+            AdminAccount::check_is_privileged(
+                &admin_data,
+                self.accounts.authority,
+                self.accounts.signers,
+            )?;
+            msg!("Admin authority validated");
+        }
+
+        let moderator_data: Ref<'_, ModeratorState> =
+            ModeratorState::load(self.accounts.moderator_account)?;
+        ModeratorAccount::check_is_valid_moderator(
+            self.accounts.moderator_account,
+            self.accounts.admin.key(),
+            &moderator_data.moderator,
+        )?;
+        drop(moderator_data);
+        msg!("Moderator account validated");
+
+        // The privileged signers pick where the rent goes
+        let moderator_lamports: u64 = self.accounts.moderator_account.lamports();
+
+        *self.accounts.moderator_account.try_borrow_mut_lamports()? -= moderator_lamports;
+        *self.accounts.destination.try_borrow_mut_lamports()? += moderator_lamports;
+        msg!("Transferred lamports to destination");
+
+        self.accounts.moderator_account.close()?;
+        msg!("Moderator revoked");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{AdminAccount, AdminState, RatingsErrors};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct SetMultisigAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetMultisigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            signers,
+        })
+    }
+}
+
+pub struct SetMultisigPayload {
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+impl TryFrom<&[u8]> for SetMultisigPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [threshold: u8][signer_count: u8][signers: [Pubkey; signer_count]]
+        // A threshold of 0 with no signers turns multisig off
+        let [threshold, signer_count, data @ ..] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        if data.len() != *signer_count as usize * 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let signers: Vec<Pubkey> = data
+            .chunks_exact(32)
+            .map(|signer| signer.try_into().unwrap())
+            .collect();
+
+        Ok(Self {
+            threshold: *threshold,
+            signers,
+        })
+    }
+}
+
+pub struct SetMultisig<'a> {
+    pub accounts: SetMultisigAccounts<'a>,
+    pub payload: SetMultisigPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for SetMultisig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: SetMultisigAccounts<'_> = SetMultisigAccounts::try_from(accounts)?;
+        let payload: SetMultisigPayload = SetMultisigPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> SetMultisig<'a> {
+    pub const DISCRIMINATOR: u8 = 12;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        // Changing the signer set needs the current approvals, not the new ones
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        // There's no queued form of this instruction, and a new signer set would control every
+        // privileged one straight away, so it's refused while a timelock delay is set
+        if admin_data.is_timelocked() {
+            return Err(RatingsErrors::TimelockActive.into());
+        }
+
+        admin_data.set_multisig(self.payload.threshold, &self.payload.signers)?;
+        msg!("Multisig updated");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{AdminAccount, AdminState};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+pub struct SetPauseAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetPauseAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            signers,
+        })
+    }
+}
+
+pub struct SetPausePayload {
+    pub paused: u8,
+}
+
+impl TryFrom<&[u8]> for SetPausePayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [paused: u8], bitmask of `AdminState::PAUSE_*`, 0 unpauses everything
+        let [paused] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self { paused: *paused })
+    }
+}
+
+pub struct SetPause<'a> {
+    pub accounts: SetPauseAccounts<'a>,
+    pub payload: SetPausePayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for SetPause<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: SetPauseAccounts<'_> = SetPauseAccounts::try_from(accounts)?;
+        let payload: SetPausePayload = SetPausePayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> SetPause<'a> {
+    pub const DISCRIMINATOR: u8 = 11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        admin_data.set_paused(self.payload.paused)?;
+        msg!("Pause flags updated");
+
+        Ok(())
+    }
+}
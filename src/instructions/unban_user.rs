@@ -0,0 +1,95 @@
+use crate::{AdminAccount, AdminState, BanAccount, BanState, ModeratorAccount};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+pub struct UnbanUserAccounts<'a> {
+    pub moderator: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub moderator_account: &'a AccountInfo,
+    pub ban: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UnbanUserAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [moderator, admin, moderator_account, ban, payer] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            moderator,
+            admin,
+            moderator_account,
+            ban,
+            payer,
+        })
+    }
+}
+
+pub struct UnbanUser<'a> {
+    pub accounts: UnbanUserAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UnbanUser<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: UnbanUserAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> UnbanUser<'a> {
+    pub const DISCRIMINATOR: u8 = 20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Bans outlive a closed catalog, its last authority can still lift them so their
+        // payers get the rent back
+        if AdminAccount::is_closed(self.accounts.admin)? {
+            AdminAccount::check_is_closed_authority(self.accounts.admin, self.accounts.moderator)?;
+            msg!("Closed admin authority validated");
+        } else {
+            let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+            AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+            msg!("Admin account validated");
+            ModeratorAccount::check_is_admin_or_moderator(
+                &admin_data,
+                self.accounts.admin,
+                self.accounts.moderator,
+                self.accounts.moderator_account,
+            )?;
+            msg!("Moderator validated");
+        }
+
+        let ban_data: Ref<'_, BanState> = BanState::load(self.accounts.ban)?;
+        BanAccount::check_is_valid_ban(
+            self.accounts.ban,
+            self.accounts.admin.key(),
+            &ban_data.user,
+        )?;
+        msg!("Ban account validated");
+
+        if &ban_data.payer != self.accounts.payer.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        drop(ban_data);
+
+        let ban_lamports: u64 = self.accounts.ban.lamports();
+
+        *self.accounts.ban.try_borrow_mut_lamports()? -= ban_lamports;
+        *self.accounts.payer.try_borrow_mut_lamports()? += ban_lamports;
+        msg!("Transferred lamports back to payer");
+
+        self.accounts.ban.close()?;
+        msg!("User unbanned");
+
+        Ok(())
+    }
+}
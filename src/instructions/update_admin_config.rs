@@ -0,0 +1,212 @@
+use crate::{AdminAccount, AdminState, RatingsErrors, RewardMode};
+use pinocchio::{
+    account_info::{AccountInfo, RefMut},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub struct UpdateAdminConfigAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateAdminConfigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, admin, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            admin,
+            signers,
+        })
+    }
+}
+
+pub struct UpdateAdminConfigPayload {
+    pub reward_amount: Option<u64>,
+    pub timelock_delay: Option<i64>,
+    pub new_authority: Option<Pubkey>,
+    pub moderation_destination: Option<Pubkey>,
+    pub allowlist_only: Option<bool>,
+    pub reward_mode: Option<(RewardMode, u64)>, // New reward mode and supply cap, set together
+}
+
+impl UpdateAdminConfigPayload {
+    // Field mask bits, set fields follow the mask in bit order
+    pub const REWARD_AMOUNT: u8 = 1 << 0;
+    pub const TIMELOCK_DELAY: u8 = 1 << 1;
+    pub const AUTHORITY: u8 = 1 << 2; // Only accepted by QueueAdminChange
+    pub const MODERATION_DESTINATION: u8 = 1 << 3;
+    pub const ALLOWLIST_ONLY: u8 = 1 << 4;
+    pub const REWARD_MODE: u8 = 1 << 5;
+    pub const ALL_FIELDS: u8 = Self::REWARD_AMOUNT
+        | Self::TIMELOCK_DELAY
+        | Self::AUTHORITY
+        | Self::MODERATION_DESTINATION
+        | Self::ALLOWLIST_ONLY
+        | Self::REWARD_MODE;
+
+    /// Splits the next `N` bytes of a field off the payload
+    fn take_field<const N: usize>(data: &[u8]) -> Result<([u8; N], &[u8]), ProgramError> {
+        if data.len() < N {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (field, data) = data.split_at(N);
+        let field: [u8; N] = field
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok((field, data))
+    }
+}
+
+impl TryFrom<&[u8]> for UpdateAdminConfigPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [field_mask: u8][reward_amount: u64 (if set)][timelock_delay: i64 (if set)]
+        // [new_authority: Pubkey (if set)][moderation_destination: Pubkey (if set)]
+        // [allowlist_only: u8 (if set)][reward_mode: u8, supply_cap: u64 (if set)]
+        let [field_mask, data @ ..] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let mut data: &[u8] = data;
+
+        if *field_mask == 0 || field_mask & !Self::ALL_FIELDS != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut payload = Self {
+            reward_amount: None,
+            timelock_delay: None,
+            new_authority: None,
+            moderation_destination: None,
+            allowlist_only: None,
+            reward_mode: None,
+        };
+
+        if field_mask & Self::REWARD_AMOUNT != 0 {
+            let (reward_amount, rest) = Self::take_field::<8>(data)?;
+            payload.reward_amount = Some(u64::from_le_bytes(reward_amount));
+            data = rest;
+        }
+
+        if field_mask & Self::TIMELOCK_DELAY != 0 {
+            let (timelock_delay, rest) = Self::take_field::<8>(data)?;
+            payload.timelock_delay = Some(i64::from_le_bytes(timelock_delay));
+            data = rest;
+        }
+
+        if field_mask & Self::AUTHORITY != 0 {
+            let (new_authority, rest) = Self::take_field::<32>(data)?;
+            payload.new_authority = Some(new_authority);
+            data = rest;
+        }
+
+        if field_mask & Self::MODERATION_DESTINATION != 0 {
+            let (moderation_destination, rest) = Self::take_field::<32>(data)?;
+            payload.moderation_destination = Some(moderation_destination);
+            data = rest;
+        }
+
+        if field_mask & Self::ALLOWLIST_ONLY != 0 {
+            let ([allowlist_only], rest) = Self::take_field::<1>(data)?;
+            payload.allowlist_only = match allowlist_only {
+                0 => Some(false),
+                1 => Some(true),
+                _ => return Err(ProgramError::InvalidInstructionData),
+            };
+            data = rest;
+        }
+
+        if field_mask & Self::REWARD_MODE != 0 {
+            let ([reward_mode], rest) = Self::take_field::<1>(data)?;
+            let (supply_cap, rest) = Self::take_field::<8>(rest)?;
+            payload.reward_mode = Some((
+                RewardMode::try_from(reward_mode)?,
+                u64::from_le_bytes(supply_cap),
+            ));
+            data = rest;
+        }
+
+        if !data.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(payload)
+    }
+}
+
+pub struct UpdateAdminConfig<'a> {
+    pub accounts: UpdateAdminConfigAccounts<'a>,
+    pub payload: UpdateAdminConfigPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for UpdateAdminConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: UpdateAdminConfigAccounts<'_> =
+            UpdateAdminConfigAccounts::try_from(accounts)?;
+        let payload: UpdateAdminConfigPayload = UpdateAdminConfigPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> UpdateAdminConfig<'a> {
+    pub const DISCRIMINATOR: u8 = 7;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut admin_data: RefMut<'_, AdminState> = AdminState::load_mut(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        AdminAccount::check_is_privileged(
+            &admin_data,
+            self.accounts.authority,
+            self.accounts.signers,
+        )?;
+        msg!("Admin authority validated");
+
+        // Authority changes go through ProposeAuthority or the timelock queue
+        if self.payload.new_authority.is_some() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if admin_data.is_timelocked() {
+            return Err(RatingsErrors::TimelockActive.into());
+        }
+
+        if let Some(reward_amount) = self.payload.reward_amount {
+            admin_data.set_reward_amount(reward_amount)?;
+            msg!("Reward amount updated");
+        }
+
+        if let Some(timelock_delay) = self.payload.timelock_delay {
+            admin_data.set_timelock_delay(timelock_delay)?;
+            msg!("Timelock delay updated");
+        }
+
+        if let Some(moderation_destination) = self.payload.moderation_destination {
+            admin_data.set_moderation_destination(moderation_destination)?;
+            msg!("Moderation destination updated");
+        }
+
+        if let Some(allowlist_only) = self.payload.allowlist_only {
+            admin_data.set_allowlist_only(allowlist_only)?;
+            msg!("Allowlist mode updated");
+        }
+
+        if let Some((reward_mode, supply_cap)) = self.payload.reward_mode {
+            admin_data.set_reward_mode(reward_mode, supply_cap)?;
+            msg!("Reward mode updated");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    AdminAccount, AdminState, ItemAccount, ItemState, RatingAccount, RatingState, RatingsErrors,
+    SignerAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    msg,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+pub struct UpdateRatingAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub rating: &'a AccountInfo,
+    pub item: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateRatingAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, rating, item, admin] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            rating,
+            item,
+            admin,
+        })
+    }
+}
+
+pub struct UpdateRatingPayload {
+    pub rating: u8,
+    pub sub_ratings: Option<Vec<u8>>,
+}
+
+impl TryFrom<&[u8]> for UpdateRatingPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [rating: u8][sub_rating_count: u8][sub_ratings]
+        // Sub-ratings are left untouched when the count is omitted
+        match data {
+            [rating] => Ok(Self {
+                rating: *rating,
+                sub_ratings: None,
+            }),
+            [rating, sub_rating_count, sub_ratings @ ..]
+                if sub_ratings.len() == *sub_rating_count as usize =>
+            {
+                Ok(Self {
+                    rating: *rating,
+                    sub_ratings: Some(sub_ratings.to_vec()),
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct UpdateRating<'a> {
+    pub accounts: UpdateRatingAccounts<'a>,
+    pub payload: UpdateRatingPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for UpdateRating<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: UpdateRatingAccounts<'_> = UpdateRatingAccounts::try_from(accounts)?;
+        let payload: UpdateRatingPayload = UpdateRatingPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> UpdateRating<'a> {
+    pub const DISCRIMINATOR: u8 = 3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.authority)?;
+        msg!("Checked if authority is signer");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin(self.accounts.admin, admin_data.catalog_id)?;
+        msg!("Admin state loaded");
+        admin_data.check_not_paused(AdminState::PAUSE_UPDATES)?;
+        msg!("Updates are not paused");
+
+        let mut rating_data: RefMut<'_, RatingState> = RatingState::load_mut(self.accounts.rating)?;
+        msg!("Loaded rating data");
+
+        if &rating_data.owner != self.accounts.authority.key() {
+            return Err(RatingsErrors::InvalidOwner.into());
+        }
+
+        RatingAccount::check_is_valid_rating(
+            self.accounts.rating,
+            self.accounts.admin.key(),
+            self.accounts.authority.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated rating account");
+        ItemAccount::check_is_valid_item(
+            self.accounts.item,
+            self.accounts.admin.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated item account");
+        rating_data.check_not_hidden()?;
+        msg!("Rating is not hidden");
+
+        let sub_ratings: Vec<u8> = match &self.payload.sub_ratings {
+            Some(sub_ratings) => sub_ratings.clone(),
+            None => rating_data.sub_ratings().to_vec(),
+        };
+        let previous_rating: RatingState = *rating_data;
+
+        // Only the scores and the update time change, no lamports or tokens move
+        rating_data.set_rating(self.payload.rating, &admin_data.rating_scale)?;
+        rating_data.set_sub_ratings(
+            &sub_ratings,
+            &admin_data.rating_scale,
+            &admin_data.rating_dimensions,
+        )?;
+        rating_data.set_updated_at(Clock::get()?.unix_timestamp)?;
+        msg!("Rating updated");
+
+        // Move the rating to its new bucket in the item aggregate
+        let mut item_data: RefMut<'_, ItemState> = ItemState::load_mut(self.accounts.item)?;
+        item_data.remove_rating(
+            previous_rating.rating,
+            previous_rating.sub_ratings(),
+            &admin_data.rating_scale,
+        )?;
+        item_data.add_rating(
+            rating_data.rating,
+            rating_data.sub_ratings(),
+            &admin_data.rating_scale,
+        )?;
+        msg!("Item aggregate updated");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    AdminAccount, AdminState, RatingAccount, RatingState, RatingsErrors, SignerAccount,
+    SystemProgramAccount,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+pub struct UpdateReviewAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub rating: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateReviewAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, rating, admin, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            rating,
+            admin,
+            system_program,
+        })
+    }
+}
+
+pub struct UpdateReviewPayload {
+    pub review: String,
+}
+
+impl TryFrom<&[u8]> for UpdateReviewPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // The whole payload is the new review, an empty payload clears it
+        if data.len() > RatingState::MAX_REVIEW_LEN {
+            return Err(RatingsErrors::ReviewTooLong.into());
+        }
+        let review =
+            String::from_utf8(data.to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        Ok(Self { review })
+    }
+}
+
+pub struct UpdateReview<'a> {
+    pub accounts: UpdateReviewAccounts<'a>,
+    pub payload: UpdateReviewPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for UpdateReview<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: UpdateReviewAccounts<'_> = UpdateReviewAccounts::try_from(accounts)?;
+        let payload: UpdateReviewPayload = UpdateReviewPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> UpdateReview<'a> {
+    pub const DISCRIMINATOR: u8 = 4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.authority)?;
+        msg!("Checked if authority is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        admin_data.check_not_paused(AdminState::PAUSE_UPDATES)?;
+        drop(admin_data);
+        msg!("Updates are not paused");
+
+        let rating_data: Ref<'_, RatingState> = RatingState::load(self.accounts.rating)?;
+        msg!("Loaded rating data");
+
+        if &rating_data.owner != self.accounts.authority.key() {
+            return Err(RatingsErrors::InvalidOwner.into());
+        }
+
+        RatingAccount::check_is_valid_rating(
+            self.accounts.rating,
+            self.accounts.admin.key(),
+            self.accounts.authority.key(),
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated rating account");
+        rating_data.check_not_hidden()?;
+        drop(rating_data);
+        msg!("Rating is not hidden");
+
+        // Settle the rent difference with the owner before resizing
+        let new_space: usize = RatingState::space(self.payload.review.len());
+        let required_lamports: u64 = Rent::get()?.minimum_balance(new_space);
+        let current_lamports: u64 = self.accounts.rating.lamports();
+
+        if required_lamports > current_lamports {
+            Transfer {
+                from: self.accounts.authority,
+                to: self.accounts.rating,
+                lamports: required_lamports - current_lamports,
+            }
+            .invoke()?;
+            msg!("Topped up rating rent from authority");
+        } else if current_lamports > required_lamports {
+            let excess_lamports: u64 = current_lamports - required_lamports;
+            *self.accounts.rating.try_borrow_mut_lamports()? -= excess_lamports;
+            *self.accounts.authority.try_borrow_mut_lamports()? += excess_lamports;
+            msg!("Refunded excess rating rent to authority");
+        }
+
+        self.accounts.rating.resize(new_space)?;
+        msg!("Resized rating account");
+
+        let mut rating_data: RefMut<'_, [u8]> = self.accounts.rating.try_borrow_mut_data()?;
+        RatingState::write_review(&mut rating_data, self.payload.review.as_bytes())?;
+        msg!("Review updated");
+
+        Ok(())
+    }
+}
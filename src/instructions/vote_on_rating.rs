@@ -0,0 +1,169 @@
+use crate::{
+    AdminAccount, AdminState, RatingAccount, RatingState, RatingsErrors, SignerAccount,
+    SystemProgramAccount, UserAccount, UserState, VoteAccount, VoteState,
+};
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub struct VoteOnRatingAccounts<'a> {
+    pub voter: &'a AccountInfo,
+    pub rating: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+    pub vote: &'a AccountInfo,
+    pub owner_user: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for VoteOnRatingAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [voter, rating, admin, vote, owner_user, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            voter,
+            rating,
+            admin,
+            vote,
+            owner_user,
+            system_program,
+        })
+    }
+}
+
+pub struct VoteOnRatingPayload {
+    pub helpful: bool,
+}
+
+impl TryFrom<&[u8]> for VoteOnRatingPayload {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // [helpful: u8], 1 for helpful and 0 for unhelpful
+        match data {
+            [0] => Ok(Self { helpful: false }),
+            [1] => Ok(Self { helpful: true }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct VoteOnRating<'a> {
+    pub accounts: VoteOnRatingAccounts<'a>,
+    pub payload: VoteOnRatingPayload,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &[u8])> for VoteOnRating<'a> {
+    type Error = ProgramError;
+
+    fn try_from(input: (&'a [AccountInfo], &[u8])) -> Result<Self, Self::Error> {
+        let (accounts, data) = input;
+        let accounts: VoteOnRatingAccounts<'_> = VoteOnRatingAccounts::try_from(accounts)?;
+        let payload: VoteOnRatingPayload = VoteOnRatingPayload::try_from(data)?;
+        Ok(Self { accounts, payload })
+    }
+}
+
+impl<'a> VoteOnRating<'a> {
+    pub const DISCRIMINATOR: u8 = 6;
+
+    // Reputation moved on the rating owner's profile per vote
+    pub const REPUTATION_PER_VOTE: i64 = 1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        SignerAccount::check_is_signer(self.accounts.voter)?;
+        msg!("Checked if voter is signer");
+        SystemProgramAccount::check_is_system_program(self.accounts.system_program)?;
+        msg!("Checked system program");
+        let admin_data: Ref<'_, AdminState> = AdminState::load(self.accounts.admin)?;
+        AdminAccount::check_is_valid_admin_with_bump(self.accounts.admin, &admin_data)?;
+        msg!("Admin account validated");
+        admin_data.check_not_paused(AdminState::PAUSE_VOTES)?;
+        drop(admin_data);
+        msg!("Votes are not paused");
+
+        let mut rating_data: RefMut<'_, RatingState> = RatingState::load_mut(self.accounts.rating)?;
+        msg!("Loaded rating data");
+
+        RatingAccount::check_is_valid_rating(
+            self.accounts.rating,
+            self.accounts.admin.key(),
+            &rating_data.owner,
+            rating_data.item_kind,
+            &rating_data.item_id,
+        )?;
+        msg!("Validated rating account");
+        rating_data.check_not_hidden()?;
+        msg!("Rating is not hidden");
+
+        if &rating_data.owner == self.accounts.voter.key() {
+            return Err(RatingsErrors::CannotVoteOnOwnRating.into());
+        }
+
+        let vote_bump: u8 = VoteAccount::check_is_valid_vote(
+            self.accounts.vote,
+            self.accounts.rating,
+            self.accounts.voter,
+        )?;
+        msg!("Validated vote account");
+        VoteAccount::check_is_empty(self.accounts.vote)?;
+        msg!("Vote account is empty");
+        UserAccount::check_is_valid_user(
+            self.accounts.owner_user,
+            self.accounts.admin.key(),
+            &rating_data.owner,
+        )?;
+        msg!("Validated rating owner's user account");
+
+        // The vote pda is never written to again, it only exists to block a second vote
+        let bump_slice: [u8; 1] = [vote_bump];
+        let vote_seeds: [Seed<'_>; 4] = [
+            Seed::from(b"vote"),
+            Seed::from(self.accounts.rating.key().as_ref()),
+            Seed::from(self.accounts.voter.key().as_ref()),
+            Seed::from(&bump_slice),
+        ];
+
+        CreateAccount {
+            from: self.accounts.voter,
+            to: self.accounts.vote,
+            lamports: Rent::get()?.minimum_balance(VoteState::LEN),
+            space: VoteState::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&vote_seeds)])?;
+        msg!("Vote account created");
+
+        let vote_state: VoteState = VoteState::set_inner(
+            *self.accounts.rating.key(),
+            *self.accounts.voter.key(),
+            self.payload.helpful,
+            vote_bump,
+        )?;
+        let mut vote_data: RefMut<'_, [u8]> = self.accounts.vote.try_borrow_mut_data()?;
+        vote_data.copy_from_slice(vote_state.as_ref());
+        msg!("Vote data serialized");
+
+        rating_data.add_vote(self.payload.helpful)?;
+        msg!("Rating tallies updated");
+
+        let reputation_delta: i64 = if self.payload.helpful {
+            Self::REPUTATION_PER_VOTE
+        } else {
+            -Self::REPUTATION_PER_VOTE
+        };
+        UserState::load_mut(self.accounts.owner_user)?.adjust_reputation(reputation_delta)?;
+        msg!("Rating owner reputation updated");
+
+        Ok(())
+    }
+}
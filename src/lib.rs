@@ -27,6 +27,67 @@ fn process_instruction(
             InitRating::try_from((accounts, data))?.process()
         }
         Some((&DeleteRating::DISCRIMINATOR, _)) => DeleteRating::try_from(accounts)?.process(),
+        Some((&UpdateRating::DISCRIMINATOR, data)) => {
+            UpdateRating::try_from((accounts, data))?.process()
+        }
+        Some((&UpdateReview::DISCRIMINATOR, data)) => {
+            UpdateReview::try_from((accounts, data))?.process()
+        }
+        Some((&RegisterItem::DISCRIMINATOR, data)) => {
+            RegisterItem::try_from((accounts, data))?.process()
+        }
+        Some((&VoteOnRating::DISCRIMINATOR, data)) => {
+            VoteOnRating::try_from((accounts, data))?.process()
+        }
+        Some((&UpdateAdminConfig::DISCRIMINATOR, data)) => {
+            UpdateAdminConfig::try_from((accounts, data))?.process()
+        }
+        Some((&ProposeAuthority::DISCRIMINATOR, _)) => {
+            ProposeAuthority::try_from(accounts)?.process()
+        }
+        Some((&AcceptAuthority::DISCRIMINATOR, _)) => {
+            AcceptAuthority::try_from(accounts)?.process()
+        }
+        Some((&CancelAuthorityTransfer::DISCRIMINATOR, _)) => {
+            CancelAuthorityTransfer::try_from(accounts)?.process()
+        }
+        Some((&SetPause::DISCRIMINATOR, data)) => SetPause::try_from((accounts, data))?.process(),
+        Some((&SetMultisig::DISCRIMINATOR, data)) => {
+            SetMultisig::try_from((accounts, data))?.process()
+        }
+        Some((&QueueAdminChange::DISCRIMINATOR, data)) => {
+            QueueAdminChange::try_from((accounts, data))?.process()
+        }
+        Some((&ExecuteAdminChange::DISCRIMINATOR, _)) => {
+            ExecuteAdminChange::try_from(accounts)?.process()
+        }
+        Some((&CancelAdminChange::DISCRIMINATOR, _)) => {
+            CancelAdminChange::try_from(accounts)?.process()
+        }
+        Some((&GrantModerator::DISCRIMINATOR, _)) => GrantModerator::try_from(accounts)?.process(),
+        Some((&RevokeModerator::DISCRIMINATOR, _)) => {
+            RevokeModerator::try_from(accounts)?.process()
+        }
+        Some((&ModerateRating::DISCRIMINATOR, data)) => {
+            ModerateRating::try_from((accounts, data))?.process()
+        }
+        Some((&BanUser::DISCRIMINATOR, data)) => BanUser::try_from((accounts, data))?.process(),
+        Some((&UnbanUser::DISCRIMINATOR, _)) => UnbanUser::try_from(accounts)?.process(),
+        Some((&AddToAllowlist::DISCRIMINATOR, data)) => {
+            AddToAllowlist::try_from((accounts, data))?.process()
+        }
+        Some((&RemoveFromAllowlist::DISCRIMINATOR, data)) => {
+            RemoveFromAllowlist::try_from((accounts, data))?.process()
+        }
+        Some((&CloseAdmin::DISCRIMINATOR, data)) => {
+            CloseAdmin::try_from((accounts, data))?.process()
+        }
+        Some((&MigrateAdmin::DISCRIMINATOR, data)) => {
+            MigrateAdmin::try_from((accounts, data))?.process()
+        }
+        Some((&MigrateAccount::DISCRIMINATOR, data)) => {
+            MigrateAccount::try_from((accounts, data))?.process()
+        }
         _ => Err(RatingsErrors::InvalidInstruction.into()),
     }
 }
@@ -3,16 +3,354 @@ use pinocchio::{
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use std::mem::size_of;
 
 use crate::errors::RatingsErrors;
 
+/// Kind of catalog item being rated, stored as a `u8` and used as a PDA seed
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Movie = 0,
+    TvEpisode = 1,
+    Book = 2,
+    Game = 3,
+}
+
+impl TryFrom<u8> for ItemKind {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Movie),
+            1 => Ok(Self::TvEpisode),
+            2 => Ok(Self::Book),
+            3 => Ok(Self::Game),
+            _ => Err(RatingsErrors::InvalidItemKind.into()),
+        }
+    }
+}
+
+/// Moderation status of a rating, stored as a `u8`
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingStatus {
+    Visible = 0,
+    Hidden = 1,  // Hidden by a moderator, left out of item aggregates
+    Removed = 2, // Hidden for good and stripped of its review, see `ModerationAction::Remove`
+}
+
+/// How InitRating pays the reward, stored as a `u8` on the admin account
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewardMode {
+    Treasury = 0, // Transferred from the admin ATA, pre-funded by InitAdmin
+    Mint = 1,     // Minted to the rater through the admin PDA's mint authority
+}
+
+impl TryFrom<u8> for RewardMode {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Treasury),
+            1 => Ok(Self::Mint),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingScale {
+    pub min: u8,  // Lowest accepted score
+    pub max: u8,  // Highest accepted score
+    pub step: u8, // Distance between accepted scores, counted from min
+}
+
+impl Default for RatingScale {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: 10,
+            step: 1,
+        }
+    }
+}
+
+impl RatingScale {
+    // Aggregates spread the scale over this many equal-width histogram buckets
+    pub const BUCKETS: usize = 10;
+
+    pub fn new(min: u8, max: u8, step: u8) -> Result<Self, ProgramError> {
+        if min >= max || step == 0 || !(max - min).is_multiple_of(step) {
+            return Err(RatingsErrors::InvalidRatingScale.into());
+        }
+
+        Ok(Self { min, max, step })
+    }
+
+    pub fn validate(&self, rating: u8) -> Result<(), ProgramError> {
+        if self.step == 0 {
+            return Err(RatingsErrors::InvalidRatingScale.into());
+        }
+
+        if !(self.min..=self.max).contains(&rating)
+            || !(rating - self.min).is_multiple_of(self.step)
+        {
+            return Err(RatingsErrors::InvalidRatingValue.into());
+        }
+
+        Ok(())
+    }
+
+    /// Histogram bucket for a valid rating, half-star or 0-100 scales are folded into
+    /// `BUCKETS` buckets while the default 1-10 scale maps one score per bucket
+    pub fn bucket(&self, rating: u8) -> Result<usize, ProgramError> {
+        self.validate(rating)?;
+
+        let steps: usize = ((self.max - self.min) / self.step) as usize + 1;
+        let index: usize = ((rating - self.min) / self.step) as usize;
+
+        Ok(index * Self::BUCKETS / steps)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RatingDimensions {
+    pub count: u8,             // Number of declared dimensions, 0 disables sub-scores
+    pub labels: [[u8; 16]; 4], // Zero padded dimension labels, e.g. "story", "acting"
+}
+
+impl RatingDimensions {
+    pub const MAX: usize = 4;
+    pub const LABEL_LEN: usize = 16;
+
+    pub fn new(labels: &[[u8; 16]]) -> Result<Self, ProgramError> {
+        if labels.len() > Self::MAX || labels.iter().any(|label| label[0] == 0) {
+            return Err(RatingsErrors::InvalidRatingDimensions.into());
+        }
+
+        let mut dimensions = Self {
+            count: labels.len() as u8,
+            ..Self::default()
+        };
+        dimensions.labels[..labels.len()].copy_from_slice(labels);
+
+        Ok(dimensions)
+    }
+
+    /// Sub-scores are optional, but when given there must be one per declared dimension
+    /// and each must sit on the rating scale
+    pub fn validate(
+        &self,
+        sub_ratings: &[u8],
+        rating_scale: &RatingScale,
+    ) -> Result<(), ProgramError> {
+        if !sub_ratings.is_empty() && sub_ratings.len() != self.count as usize {
+            return Err(RatingsErrors::InvalidSubRatings.into());
+        }
+
+        sub_ratings
+            .iter()
+            .try_for_each(|sub_rating| rating_scale.validate(*sub_rating))
+    }
+}
+
+/// Layout version of program accounts, stored in the account header. Accounts created
+/// before the header was added have none and count as version 1
+pub const ACCOUNT_VERSION: u8 = 4;
+
+// Version 2 headers predate the account type and leave it zeroed
+pub const UNTYPED_VERSION: u8 = 2;
+
+// First version whose header carries the account type
+pub const TYPED_VERSION: u8 = 3;
+
+/// Kind of program account, stored in the account header so one type can't be passed as another
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Admin = 1,
+    Rating = 2,
+    Item = 3,
+    User = 4,
+    Vote = 5,
+    PendingChange = 6,
+    Moderator = 7,
+    Ban = 8,
+    Allowlist = 9,
+    ClosedAdmin = 10, // Tombstone `close_admin` leaves at the admin address
+}
+
+impl AccountType {
+    /// Oldest header version whose layout for this type is still current. Version 4 appended
+    /// the supply cap to admins and pending changes and the payer to allowlist entries, and
+    /// left the other types as they were
+    #[inline(always)]
+    pub const fn layout_version(self) -> u8 {
+        match self {
+            Self::Admin | Self::PendingChange | Self::Allowlist => 4,
+            _ => TYPED_VERSION,
+        }
+    }
+}
+
+impl TryFrom<u8> for AccountType {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Admin),
+            2 => Ok(Self::Rating),
+            3 => Ok(Self::Item),
+            4 => Ok(Self::User),
+            5 => Ok(Self::Vote),
+            6 => Ok(Self::PendingChange),
+            7 => Ok(Self::Moderator),
+            8 => Ok(Self::Ban),
+            9 => Ok(Self::Allowlist),
+            10 => Ok(Self::ClosedAdmin),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Leads every program account, the state after it starts at `AccountHeader::LEN`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountHeader {
+    pub version: u8,
+    pub account_type: u8,  // AccountType of the state after the header
+    pub reserved: [u8; 6], // Keeps the state after the header 8-byte aligned
+}
+
+impl AsRef<[u8]> for AccountHeader {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl AccountHeader {
+    pub const LEN: usize = 8;
+
+    #[inline(always)]
+    pub const fn new(account_type: AccountType) -> Self {
+        Self {
+            version: ACCOUNT_VERSION,
+            account_type: account_type as u8,
+            reserved: [0u8; 6],
+        }
+    }
+
+    /// Grows the account to `new_len` and writes a current header for `account_type` over an
+    /// older one. Version 1 data is moved behind the header first. The account must already
+    /// hold the rent for `new_len`, and the bytes past the old data are zeroed
+    pub fn upgrade(
+        account: &AccountInfo,
+        version: u8,
+        new_len: usize,
+        account_type: AccountType,
+    ) -> Result<(), ProgramError> {
+        let old_len: usize = account.data_len();
+
+        if version == 1 {
+            if new_len < old_len + Self::LEN {
+                return Err(ProgramError::InvalidRealloc);
+            }
+
+            account.resize(new_len)?;
+            account
+                .try_borrow_mut_data()?
+                .copy_within(..old_len, Self::LEN);
+        } else if (UNTYPED_VERSION..ACCOUNT_VERSION).contains(&version) {
+            if new_len < old_len {
+                return Err(ProgramError::InvalidRealloc);
+            }
+
+            account.resize(new_len)?;
+        } else {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut data: RefMut<'_, [u8]> = account.try_borrow_mut_data()?;
+        data[..Self::LEN].copy_from_slice(Self::new(account_type).as_ref());
+
+        Ok(())
+    }
+
+    /// Returns the header version of an account whose size fits a layout with a header, or
+    /// `None` if it doesn't start with a known version. Fails with `InvalidAccountType` if a
+    /// typed header is for another type
+    fn version(data: &[u8], account_type: AccountType) -> Result<Option<u8>, ProgramError> {
+        match data {
+            [UNTYPED_VERSION, ..] => Ok(Some(UNTYPED_VERSION)),
+            [version, kind, ..] if (TYPED_VERSION..=ACCOUNT_VERSION).contains(version) => {
+                if *kind != account_type as u8 {
+                    return Err(RatingsErrors::InvalidAccountType.into());
+                }
+                Ok(Some(*version))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Checks a fixed-size account is a current-layout `account_type` of `len` bytes. A version 1
+/// account, one header shorter, or one with an older header fails with `AccountNeedsMigration`
+fn check_version(data: &[u8], len: usize, account_type: AccountType) -> Result<(), ProgramError> {
+    check_layout_version(data, len, len, account_type)
+}
+
+/// `check_version` for a type whose layout grew in the current version, older accounts of it
+/// are `legacy_len` bytes, or one header shorter for version 1
+fn check_layout_version(
+    data: &[u8],
+    len: usize,
+    legacy_len: usize,
+    account_type: AccountType,
+) -> Result<(), ProgramError> {
+    // Version 1 accounts have no header, so only their size tells them apart
+    if data.len() == legacy_len - AccountHeader::LEN {
+        return Err(RatingsErrors::AccountNeedsMigration.into());
+    }
+
+    if data.len() != len && data.len() != legacy_len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match AccountHeader::version(data, account_type)? {
+        Some(version) if version >= account_type.layout_version() && data.len() == len => Ok(()),
+        Some(version) if version < account_type.layout_version() && data.len() == legacy_len => {
+            Err(RatingsErrors::AccountNeedsMigration.into())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
 #[repr(C)]
 pub struct AdminState {
+    pub header: AccountHeader,
     pub authority: Pubkey,
+    pub pending_authority: Pubkey, // Proposed authority awaiting acceptance, all zeros if none
     pub token_mint: Pubkey,
     pub reward_amount: u64,
+    pub catalog_id: u64, // Namespace the admin PDA is seeded with
     pub bump: u8,
+    pub paused: u8, // Bitmask of paused instruction groups, see `AdminState::PAUSE_*`
+    pub rating_scale: RatingScale,
+    pub rating_dimensions: RatingDimensions,
+    pub multisig_threshold: u8, // Approvals needed for privileged instructions, 0 when multisig is off
+    pub multisig_count: u8,     // Number of keys in `multisig_signers`
+    pub multisig_signers: [Pubkey; AdminState::MAX_MULTISIG_SIGNERS],
+    pub timelock_delay: i64, // Seconds queued config changes wait before execution, 0 when off
+    pub moderation_destination: Pubkey, // Receives the rent of ratings removed by moderation
+    pub allowlist_only: u8,  // 1 if only allowlisted wallets can submit ratings
+    pub reward_mode: u8,     // RewardMode of InitRating
+    pub total_ratings: u64, // Visible ratings in the catalog, hidden and deleted ones are not counted
+    pub total_rewards_paid: u64, // Reward tokens paid out over the lifetime of the catalog
+    pub active_raters: u64, // Users with at least one visible rating
+    pub supply_cap: u64,    // Max supply of the reward mint, enforced on every mint, 0 for no cap
 }
 
 impl AsRef<[u8]> for AdminState {
@@ -23,31 +361,63 @@ impl AsRef<[u8]> for AdminState {
 }
 
 impl AdminState {
-    pub const LEN: usize = 32 + 32 + 8 + 8; // 8 bytes for bump to ensure memory alignment
+    pub const LEN: usize =
+        AccountHeader::LEN + 32 + 32 + 32 + 8 + 8 + 8 + 16 * 4 + 32 * 5 + 8 + 32 + 8 + 8 * 3 + 8; // 8 bytes for bump, paused, rating_scale, the dimension count and multisig sizes, and 8 for allowlist_only and reward_mode, to ensure memory alignment
+
+    // Size of version 2 and 3 admin accounts, from before the supply cap, see `MigrateAccount`
+    pub const PRE_SUPPLY_CAP_LEN: usize = Self::LEN - 8;
+
+    // Size of version 1 admin accounts created before the counters were added, see `MigrateAdmin`
+    pub const PRE_COUNTERS_LEN: usize = Self::PRE_SUPPLY_CAP_LEN - AccountHeader::LEN - 8 * 3;
+
+    pub const MAX_MULTISIG_SIGNERS: usize = 5;
 
+    // Pause bits, DeleteRating is never paused so users can always reclaim their rent
+    pub const PAUSE_RATINGS: u8 = 1 << 0; // InitRating and its reward
+    pub const PAUSE_UPDATES: u8 = 1 << 1; // UpdateRating and UpdateReview
+    pub const PAUSE_VOTES: u8 = 1 << 2; // VoteOnRating
+    pub const PAUSE_ALL: u8 = Self::PAUSE_RATINGS | Self::PAUSE_UPDATES | Self::PAUSE_VOTES;
+
+    /// Admins from before the counters are also one header shorter than version 2 ones, but
+    /// need `MigrateAdmin` rather than `MigrateAccount`
     #[inline(always)]
-    pub fn load(account: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
-        if account.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
+    fn check_version(data: &[u8]) -> Result<(), ProgramError> {
+        if data.len() == Self::PRE_COUNTERS_LEN {
+            return Err(RatingsErrors::AccountNeedsMigration.into());
         }
+
+        check_layout_version(
+            data,
+            Self::LEN,
+            Self::PRE_SUPPLY_CAP_LEN,
+            AccountType::Admin,
+        )
+    }
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
         if account.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
 
-        Ok(Ref::map(account.try_borrow_data()?, |data| unsafe {
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        Self::check_version(&data)?;
+
+        Ok(Ref::map(data, |data| unsafe {
             &*(data.as_ptr() as *const AdminState)
         }))
     }
 
     #[inline(always)]
-    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<AdminState>, ProgramError> {
-        if account.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<'_, AdminState>, ProgramError> {
         if account.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(RefMut::map(account.try_borrow_mut_data()?, |data| unsafe {
+
+        let data: RefMut<'_, [u8]> = account.try_borrow_mut_data()?;
+        Self::check_version(&data)?;
+
+        Ok(RefMut::map(data, |data| unsafe {
             &mut *(data.as_mut_ptr() as *mut AdminState)
         }))
     }
@@ -58,6 +428,17 @@ impl AdminState {
         Ok(())
     }
 
+    #[inline(always)]
+    pub fn set_pending_authority(&mut self, pending_authority: Pubkey) -> Result<(), ProgramError> {
+        self.pending_authority = pending_authority;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn has_pending_authority(&self) -> bool {
+        self.pending_authority != Pubkey::default()
+    }
+
     #[inline(always)]
     pub fn set_token_mint(&mut self, token_mint: Pubkey) -> Result<(), ProgramError> {
         self.token_mint = token_mint;
@@ -70,118 +451,1142 @@ impl AdminState {
         Ok(())
     }
 
+    #[inline(always)]
+    pub fn set_catalog_id(&mut self, catalog_id: u64) -> Result<(), ProgramError> {
+        self.catalog_id = catalog_id;
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn set_bump(&mut self, bump: u8) -> Result<(), ProgramError> {
         self.bump = bump;
         Ok(())
     }
-}
 
-#[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct RatingState {
-    pub movie_title: [u8; 32], // Movie title (max 32 chars)
-    pub rating: u8,            // Rating 1-10
-    pub owner: Pubkey,         // User who created the rating
-    pub timestamp: i64,        // Unix timestamp of creation
-    pub bump: u8,
-}
+    pub fn set_paused(&mut self, paused: u8) -> Result<(), ProgramError> {
+        if paused & !Self::PAUSE_ALL != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
-impl AsRef<[u8]> for RatingState {
-    fn as_ref(&self) -> &[u8] {
-        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+        self.paused = paused;
+        Ok(())
     }
-}
 
-impl RatingState {
-    pub const LEN: usize = 32 + 8 + 32 + 8 + 8; // 8 bytes for rating and bump to ensure memory alignment
+    #[inline(always)]
+    pub fn set_moderation_destination(
+        &mut self,
+        moderation_destination: Pubkey,
+    ) -> Result<(), ProgramError> {
+        if moderation_destination == Pubkey::default() {
+            return Err(RatingsErrors::InvalidModerationDestination.into());
+        }
+
+        self.moderation_destination = moderation_destination;
+        Ok(())
+    }
 
     #[inline(always)]
-    pub fn load(account: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
-        if account.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
+    pub fn is_allowlist_only(&self) -> bool {
+        self.allowlist_only != 0
+    }
+
+    #[inline(always)]
+    pub fn set_allowlist_only(&mut self, allowlist_only: bool) -> Result<(), ProgramError> {
+        self.allowlist_only = allowlist_only as u8;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn is_mint_rewards(&self) -> bool {
+        self.reward_mode == RewardMode::Mint as u8
+    }
+
+    #[inline(always)]
+    pub fn set_reward_mode(
+        &mut self,
+        reward_mode: RewardMode,
+        supply_cap: u64,
+    ) -> Result<(), ProgramError> {
+        self.reward_mode = reward_mode as u8;
+        self.supply_cap = supply_cap;
+        Ok(())
+    }
+
+    /// Checks minting `amount` on top of the mint's current `supply` stays within the cap
+    pub fn check_supply_cap(&self, supply: u64, amount: u64) -> Result<(), ProgramError> {
+        if self.supply_cap == 0 {
+            return Ok(());
         }
 
-        if account.owner() != &crate::ID {
-            return Err(ProgramError::InvalidAccountOwner);
+        let new_supply: u64 = supply
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if new_supply > self.supply_cap {
+            return Err(RatingsErrors::SupplyCapExceeded.into());
         }
 
-        Ok(Ref::map(account.try_borrow_data()?, |data: &[u8]| unsafe {
-            &*(data.as_ptr() as *const RatingState)
-        }))
+        Ok(())
     }
 
-    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<Self>, ProgramError> {
-        if account.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
+    #[inline(always)]
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock_delay > 0
+    }
+
+    pub fn set_timelock_delay(&mut self, timelock_delay: i64) -> Result<(), ProgramError> {
+        if timelock_delay < 0 {
+            return Err(ProgramError::InvalidInstructionData);
         }
 
-        if account.owner() != &crate::ID {
-            return Err(ProgramError::InvalidAccountOwner);
+        self.timelock_delay = timelock_delay;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn is_multisig(&self) -> bool {
+        self.multisig_threshold > 0
+    }
+
+    #[inline(always)]
+    pub fn multisig_signers(&self) -> &[Pubkey] {
+        &self.multisig_signers[..self.multisig_count as usize]
+    }
+
+    /// Sets the multisig keys and threshold, an empty key list with threshold 0 turns multisig off
+    pub fn set_multisig(&mut self, threshold: u8, signers: &[Pubkey]) -> Result<(), ProgramError> {
+        if signers.len() > Self::MAX_MULTISIG_SIGNERS
+            || threshold as usize > signers.len()
+            || (threshold == 0) != signers.is_empty()
+        {
+            return Err(RatingsErrors::InvalidMultisig.into());
         }
 
-        Ok(RefMut::map(account.try_borrow_mut_data()?, |data| unsafe {
-            &mut *(data.as_mut_ptr() as *mut RatingState)
-        }))
+        for (i, signer) in signers.iter().enumerate() {
+            if signer == &Pubkey::default() || signers[..i].contains(signer) {
+                return Err(RatingsErrors::InvalidMultisig.into());
+            }
+        }
+
+        self.multisig_threshold = threshold;
+        self.multisig_count = signers.len() as u8;
+        self.multisig_signers = [Pubkey::default(); Self::MAX_MULTISIG_SIGNERS];
+        self.multisig_signers[..signers.len()].copy_from_slice(signers);
+
+        Ok(())
     }
 
-    pub fn set_movie_title(&mut self, movie_title: String) -> Result<(), ProgramError> {
-        if movie_title.len() > 32 {
-            return Err(RatingsErrors::MovieTitleTooLong.into());
+    #[inline(always)]
+    pub fn check_not_paused(&self, pause_bit: u8) -> Result<(), ProgramError> {
+        if self.paused & pause_bit != 0 {
+            return Err(RatingsErrors::ProgramPaused.into());
         }
 
-        let mut movie_title_array = [0u8; 32];
-        let movie_title_bytes = movie_title.as_bytes();
-        movie_title_array[..movie_title_bytes.len()].copy_from_slice(movie_title_bytes);
-        self.movie_title = movie_title_array;
+        Ok(())
+    }
+
+    /// Counts a new visible rating and its reward, `new_rater` is set on the user's first
+    /// visible rating
+    pub fn record_rating(
+        &mut self,
+        reward_amount: u64,
+        new_rater: bool,
+    ) -> Result<(), ProgramError> {
+        self.total_ratings = self
+            .total_ratings
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_rewards_paid = self
+            .total_rewards_paid
+            .checked_add(reward_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if new_rater {
+            self.active_raters = self
+                .active_raters
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
 
         Ok(())
     }
 
-    pub fn set_rating(&mut self, rating: u8) -> Result<(), ProgramError> {
-        if !(1..=10).contains(&rating) {
-            return Err(RatingsErrors::InvalidRatingValue.into());
+    /// Uncounts a visible rating that was deleted, hidden or removed. Saturates, since a
+    /// migrated admin only counts from the values it was migrated with
+    pub fn remove_rating(&mut self, rater_left: bool) -> Result<(), ProgramError> {
+        self.total_ratings = self.total_ratings.saturating_sub(1);
+        if rater_left {
+            self.active_raters = self.active_raters.saturating_sub(1);
         }
 
-        self.rating = rating;
+        Ok(())
+    }
+
+    pub fn set_counters(
+        &mut self,
+        total_ratings: u64,
+        total_rewards_paid: u64,
+        active_raters: u64,
+    ) -> Result<(), ProgramError> {
+        if active_raters > total_ratings {
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
+        self.total_ratings = total_ratings;
+        self.total_rewards_paid = total_rewards_paid;
+        self.active_raters = active_raters;
         Ok(())
     }
 
     #[inline(always)]
-    pub fn set_owner(&mut self, owner: Pubkey) -> Result<(), ProgramError> {
-        self.owner = owner;
+    pub fn set_rating_scale(&mut self, rating_scale: RatingScale) -> Result<(), ProgramError> {
+        self.rating_scale = rating_scale;
         Ok(())
     }
 
     #[inline(always)]
-    pub fn set_timestamp(&mut self, timestamp: i64) -> Result<(), ProgramError> {
-        self.timestamp = timestamp;
+    pub fn set_rating_dimensions(
+        &mut self,
+        rating_dimensions: RatingDimensions,
+    ) -> Result<(), ProgramError> {
+        self.rating_dimensions = rating_dimensions;
         Ok(())
     }
+}
+
+// Variable-length fields live after an account's fixed header: [u16 LE length][bytes]
+pub const TRAILING_LEN_PREFIX: usize = 2;
+
+/// Reads the trailing length prefix and checks the account is sized to fit it exactly
+fn check_trailing_len(data: &[u8], header_len: usize) -> Result<usize, ProgramError> {
+    if data.len() < header_len + TRAILING_LEN_PREFIX {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let trailing_len = u16::from_le_bytes([data[header_len], data[header_len + 1]]) as usize;
+    if data.len() != header_len + TRAILING_LEN_PREFIX + trailing_len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(trailing_len)
+}
+
+/// `check_trailing_len` for a current-layout `account_type`. A version 1 account, whose
+/// trailing field starts one header earlier, or a version 2 one fails with
+/// `AccountNeedsMigration`
+fn check_trailing_version(
+    data: &[u8],
+    header_len: usize,
+    account_type: AccountType,
+) -> Result<usize, ProgramError> {
+    // The header is only read once the size fits a layout with one
+    if let Ok(trailing_len) = check_trailing_len(data, header_len) {
+        match AccountHeader::version(data, account_type)? {
+            Some(version) if version >= account_type.layout_version() => return Ok(trailing_len),
+            Some(_) => return Err(RatingsErrors::AccountNeedsMigration.into()),
+            None => {}
+        }
+    }
+
+    if check_trailing_len(data, header_len - AccountHeader::LEN).is_ok() {
+        return Err(RatingsErrors::AccountNeedsMigration.into());
+    }
 
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// Writes the length prefix and bytes, `data` must already be sized to fit them exactly
+fn write_trailing(data: &mut [u8], header_len: usize, bytes: &[u8]) -> Result<(), ProgramError> {
+    if data.len() != header_len + TRAILING_LEN_PREFIX + bytes.len() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    data[header_len..header_len + TRAILING_LEN_PREFIX]
+        .copy_from_slice(&(bytes.len() as u16).to_le_bytes());
+    data[header_len + TRAILING_LEN_PREFIX..].copy_from_slice(bytes);
+
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingState {
+    pub header: AccountHeader,
+    pub item_id: [u8; 32],    // ID of the registered item
+    pub item_kind: u8,        // ItemKind of the registered item
+    pub rating: u8,           // Rating on the admin rating scale
+    pub sub_rating_count: u8, // Number of sub-scores given, 0 or the admin dimension count
+    pub sub_ratings: [u8; 4], // Sub-scores in admin dimension order
+    pub owner: Pubkey,        // User who created the rating
+    pub timestamp: i64,       // Unix timestamp of creation
+    pub updated_at: i64,      // Unix timestamp of the last score change
+    pub helpful_count: u32,   // Votes marking the rating helpful
+    pub unhelpful_count: u32, // Votes marking the rating unhelpful
+    pub bump: u8,
+    pub status: u8,            // RatingStatus, set by moderation
+    pub moderation_reason: u8, // Reason code recorded when the rating was hidden
+}
+
+impl AsRef<[u8]> for RatingState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl RatingState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 8 + 32 + 8 + 8 + 4 + 4 + 8; // 8 bytes for item kind, rating, sub-ratings, bump, status and moderation reason to ensure memory alignment
+
+    // Review text is the trailing UTF-8 field
+    pub const MAX_REVIEW_LEN: usize = 256;
+
+    /// Account size for a rating carrying a review of `review_len` bytes
     #[inline(always)]
-    pub fn set_inner(
-        movie_title: String, // Movie title (max 32 chars)
-        rating: u8,          // Rating 1-10
-        owner: Pubkey,       // User who created the rating
-        timestamp: i64,      // Unix timestamp of creation
-        bump: u8,
-    ) -> Result<Self, ProgramError> {
-        if movie_title.len() > 32 {
-            return Err(RatingsErrors::MovieTitleTooLong.into());
+    pub const fn space(review_len: usize) -> usize {
+        Self::LEN + TRAILING_LEN_PREFIX + review_len
+    }
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
         }
 
-        let mut movie_title_array: [u8; 32] = [0u8; 32];
-        let movie_title_bytes: &[u8] = movie_title.as_bytes();
-        movie_title_array[..movie_title_bytes.len()].copy_from_slice(movie_title_bytes);
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Rating)?;
 
-        Ok(Self {
-            movie_title: movie_title_array,
-            rating,
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const RatingState)
+        }))
+    }
+
+    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: RefMut<'_, [u8]> = account.try_borrow_mut_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Rating)?;
+
+        Ok(RefMut::map(data, |data| unsafe {
+            &mut *(data.as_mut_ptr() as *mut RatingState)
+        }))
+    }
+
+    pub fn load_review(account: &AccountInfo) -> Result<Ref<'_, [u8]>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Rating)?;
+
+        Ok(Ref::map(data, |data: &[u8]| {
+            &data[Self::LEN + TRAILING_LEN_PREFIX..]
+        }))
+    }
+
+    /// Writes the length prefix and review bytes, `data` must be `space(review.len())` long
+    pub fn write_review(data: &mut [u8], review: &[u8]) -> Result<(), ProgramError> {
+        if review.len() > Self::MAX_REVIEW_LEN {
+            return Err(RatingsErrors::ReviewTooLong.into());
+        }
+
+        write_trailing(data, Self::LEN, review)
+    }
+
+    #[inline(always)]
+    pub fn set_item_id(&mut self, item_id: [u8; 32]) -> Result<(), ProgramError> {
+        self.item_id = item_id;
+        Ok(())
+    }
+
+    pub fn set_rating(
+        &mut self,
+        rating: u8,
+        rating_scale: &RatingScale,
+    ) -> Result<(), ProgramError> {
+        rating_scale.validate(rating)?;
+
+        self.rating = rating;
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn sub_ratings(&self) -> &[u8] {
+        &self.sub_ratings[..self.sub_rating_count as usize]
+    }
+
+    pub fn set_sub_ratings(
+        &mut self,
+        sub_ratings: &[u8],
+        rating_scale: &RatingScale,
+        rating_dimensions: &RatingDimensions,
+    ) -> Result<(), ProgramError> {
+        rating_dimensions.validate(sub_ratings, rating_scale)?;
+
+        self.sub_rating_count = sub_ratings.len() as u8;
+        self.sub_ratings = [0u8; RatingDimensions::MAX];
+        self.sub_ratings[..sub_ratings.len()].copy_from_slice(sub_ratings);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_owner(&mut self, owner: Pubkey) -> Result<(), ProgramError> {
+        self.owner = owner;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_timestamp(&mut self, timestamp: i64) -> Result<(), ProgramError> {
+        self.timestamp = timestamp;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_updated_at(&mut self, updated_at: i64) -> Result<(), ProgramError> {
+        self.updated_at = updated_at;
+        Ok(())
+    }
+
+    /// Whether moderation took the rating out of the aggregates, by hiding or removing it
+    #[inline(always)]
+    pub fn is_hidden(&self) -> bool {
+        self.status != RatingStatus::Visible as u8
+    }
+
+    #[inline(always)]
+    pub fn is_removed(&self) -> bool {
+        self.status == RatingStatus::Removed as u8
+    }
+
+    /// Fails with `RatingHidden` or `RatingRemoved` so moderated ratings can't be edited or
+    /// voted on
+    #[inline(always)]
+    pub fn check_not_hidden(&self) -> Result<(), ProgramError> {
+        if self.is_removed() {
+            return Err(RatingsErrors::RatingRemoved.into());
+        }
+
+        if self.is_hidden() {
+            return Err(RatingsErrors::RatingHidden.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn hide(&mut self, reason: u8) -> Result<(), ProgramError> {
+        self.check_not_hidden()?;
+
+        self.status = RatingStatus::Hidden as u8;
+        self.moderation_reason = reason;
+        Ok(())
+    }
+
+    /// Removing a hidden rating is allowed, removing it twice is not
+    pub fn remove(&mut self, reason: u8) -> Result<(), ProgramError> {
+        if self.is_removed() {
+            return Err(RatingsErrors::RatingRemoved.into());
+        }
+
+        self.status = RatingStatus::Removed as u8;
+        self.moderation_reason = reason;
+        Ok(())
+    }
+
+    pub fn add_vote(&mut self, helpful: bool) -> Result<(), ProgramError> {
+        let tally: &mut u32 = if helpful {
+            &mut self.helpful_count
+        } else {
+            &mut self.unhelpful_count
+        };
+        *tally = tally
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        item_id: [u8; 32],          // ID of the registered item
+        item_kind: ItemKind,        // Kind of the registered item
+        rating: u8,                 // Rating on the admin rating scale
+        rating_scale: &RatingScale, // Admin rating scale
+        owner: Pubkey,              // User who created the rating
+        timestamp: i64,             // Unix timestamp of creation
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        rating_scale.validate(rating)?;
+
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Rating),
+            item_id,
+            item_kind: item_kind as u8,
+            rating,
+            sub_rating_count: 0,
+            sub_ratings: [0u8; RatingDimensions::MAX],
             owner,
             timestamp,
+            updated_at: timestamp,
+            helpful_count: 0,
+            unhelpful_count: 0,
+            bump,
+            status: RatingStatus::Visible as u8,
+            moderation_reason: 0,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemState {
+    pub header: AccountHeader,
+    pub item_id: [u8; 32], // sha256 of the normalized title, used as the item and rating PDA seed
+    pub external_id: [u8; 16], // External catalogue ID, e.g. an IMDb ID or ISBN
+    pub item_kind: u8,     // ItemKind, part of the item and rating PDA seeds
+    pub release_year: u16, // Year of release
+    pub rating_count: u64, // Number of live ratings
+    pub rating_sum: u64,   // Sum of live ratings
+    pub histogram: [u64; RatingScale::BUCKETS], // Live ratings per bucket of the admin rating scale
+    pub dimension_counts: [u64; RatingDimensions::MAX], // Live ratings carrying sub-scores
+    pub dimension_sums: [u64; RatingDimensions::MAX], // Sum of live sub-scores per dimension
+    pub bump: u8,
+}
+
+impl AsRef<[u8]> for ItemState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl ItemState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 16 + 8 + 8 + 8 + 8 * 10 + 8 * 4 + 8 * 4 + 8; // 8 bytes for item_kind, release_year and bump to ensure memory alignment
+
+    // Full display title is the trailing UTF-8 field
+    pub const MAX_TITLE_LEN: usize = 128;
+
+    /// Account size for an item with a title of `title_len` bytes
+    #[inline(always)]
+    pub const fn space(title_len: usize) -> usize {
+        Self::LEN + TRAILING_LEN_PREFIX + title_len
+    }
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Item)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const ItemState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: RefMut<'_, [u8]> = account.try_borrow_mut_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Item)?;
+
+        Ok(RefMut::map(data, |data| unsafe {
+            &mut *(data.as_mut_ptr() as *mut ItemState)
+        }))
+    }
+
+    pub fn load_title(account: &AccountInfo) -> Result<Ref<'_, [u8]>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_trailing_version(&data, Self::LEN, AccountType::Item)?;
+
+        Ok(Ref::map(data, |data: &[u8]| {
+            &data[Self::LEN + TRAILING_LEN_PREFIX..]
+        }))
+    }
+
+    /// Writes the length prefix and title bytes, `data` must be `space(title.len())` long
+    pub fn write_title(data: &mut [u8], title: &[u8]) -> Result<(), ProgramError> {
+        if title.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if title.len() > Self::MAX_TITLE_LEN {
+            return Err(RatingsErrors::ItemTitleTooLong.into());
+        }
+
+        write_trailing(data, Self::LEN, title)
+    }
+
+    pub fn add_rating(
+        &mut self,
+        rating: u8,
+        sub_ratings: &[u8],
+        rating_scale: &RatingScale,
+    ) -> Result<(), ProgramError> {
+        let bucket = rating_scale.bucket(rating)?;
+
+        self.rating_count = self
+            .rating_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.rating_sum = self
+            .rating_sum
+            .checked_add(rating as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.histogram[bucket] = self.histogram[bucket]
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        for (dimension, sub_rating) in sub_ratings.iter().enumerate() {
+            self.dimension_counts[dimension] = self.dimension_counts[dimension]
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            self.dimension_sums[dimension] = self.dimension_sums[dimension]
+                .checked_add(*sub_rating as u64)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_rating(
+        &mut self,
+        rating: u8,
+        sub_ratings: &[u8],
+        rating_scale: &RatingScale,
+    ) -> Result<(), ProgramError> {
+        let bucket = rating_scale.bucket(rating)?;
+
+        self.rating_count = self
+            .rating_count
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.rating_sum = self
+            .rating_sum
+            .checked_sub(rating as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.histogram[bucket] = self.histogram[bucket]
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        for (dimension, sub_rating) in sub_ratings.iter().enumerate() {
+            self.dimension_counts[dimension] = self.dimension_counts[dimension]
+                .checked_sub(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            self.dimension_sums[dimension] = self.dimension_sums[dimension]
+                .checked_sub(*sub_rating as u64)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        item_id: [u8; 32],
+        external_id: [u8; 16],
+        item_kind: ItemKind,
+        release_year: u16,
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Item),
+            item_id,
+            external_id,
+            item_kind: item_kind as u8,
+            release_year,
+            rating_count: 0,
+            rating_sum: 0,
+            histogram: [0u64; RatingScale::BUCKETS],
+            dimension_counts: [0u64; RatingDimensions::MAX],
+            dimension_sums: [0u64; RatingDimensions::MAX],
+            bump,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserState {
+    pub header: AccountHeader,
+    pub authority: Pubkey,    // Wallet the profile belongs to
+    pub rating_count: u64,    // Ratings submitted over the lifetime of the profile
+    pub total_rewards: u64,   // Reward tokens earned over the lifetime of the profile
+    pub first_rating_at: i64, // Unix timestamp of the first rating
+    pub last_rating_at: i64,  // Unix timestamp of the most recent rating
+    pub reputation: i64,      // Reputation score other features can read and adjust
+    pub bump: u8,
+    pub tracks_active_ratings: u8, // 1 if `active_ratings` was counted since the profile was created
+    pub active_ratings: u32, // Visible ratings the user currently has, counted from when it was added
+}
+
+impl AsRef<[u8]> for UserState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl UserState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 8 + 8 + 8 + 8 + 8 + 8; // 8 bytes for bump, tracks_active_ratings and active_ratings to ensure memory alignment
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_version(&data, Self::LEN, AccountType::User)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const UserState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn load_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: RefMut<'_, [u8]> = account.try_borrow_mut_data()?;
+        check_version(&data, Self::LEN, AccountType::User)?;
+
+        Ok(RefMut::map(data, |data| unsafe {
+            &mut *(data.as_mut_ptr() as *mut UserState)
+        }))
+    }
+
+    pub fn record_rating(
+        &mut self,
+        timestamp: i64,
+        reward_amount: u64,
+    ) -> Result<(), ProgramError> {
+        self.rating_count = self
+            .rating_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_rewards = self
+            .total_rewards
+            .checked_add(reward_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if self.first_rating_at == 0 {
+            self.first_rating_at = timestamp;
+        }
+        self.last_rating_at = timestamp;
+
+        Ok(())
+    }
+
+    /// Counts a visible rating, returns true if it is the user's only one. Profiles created
+    /// before `active_ratings` was added are already in the backfilled `active_raters` and
+    /// their count misses their older ratings, so those never count as joining
+    pub fn add_active_rating(&mut self) -> Result<bool, ProgramError> {
+        self.active_ratings = self
+            .active_ratings
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(self.tracks_active_ratings == 1 && self.active_ratings == 1)
+    }
+
+    /// Uncounts a visible rating, returns true if the user has none left. Profiles created
+    /// before `active_ratings` was added never count as leaving, for the same reason
+    pub fn remove_active_rating(&mut self) -> Result<bool, ProgramError> {
+        if self.active_ratings == 0 {
+            return Ok(false);
+        }
+
+        self.active_ratings -= 1;
+        Ok(self.tracks_active_ratings == 1 && self.active_ratings == 0)
+    }
+
+    pub fn adjust_reputation(&mut self, delta: i64) -> Result<(), ProgramError> {
+        self.reputation = self
+            .reputation
+            .checked_add(delta)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(authority: Pubkey, bump: u8) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::User),
+            authority,
+            rating_count: 0,
+            total_rewards: 0,
+            first_rating_at: 0,
+            last_rating_at: 0,
+            reputation: 0,
+            bump,
+            tracks_active_ratings: 1,
+            active_ratings: 0,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoteState {
+    pub header: AccountHeader,
+    pub rating: Pubkey, // Rating account voted on
+    pub voter: Pubkey,  // Wallet that cast the vote
+    pub helpful: u8,    // 1 if the vote marked the rating helpful, 0 otherwise
+    pub bump: u8,
+}
+
+impl AsRef<[u8]> for VoteState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl VoteState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 32 + 1 + 1;
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_version(&data, Self::LEN, AccountType::Vote)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const VoteState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        rating: Pubkey,
+        voter: Pubkey,
+        helpful: bool,
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Vote),
+            rating,
+            voter,
+            helpful: helpful as u8,
+            bump,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingChangeState {
+    pub header: AccountHeader,
+    pub admin: Pubkey,                  // Admin account the change applies to
+    pub payer: Pubkey, // Receives the rent back when the change is executed or cancelled
+    pub new_authority: Pubkey, // Proposed authority, all zeros unless the authority bit is set
+    pub moderation_destination: Pubkey, // New moderation rent destination, if its bit is set
+    pub reward_amount: u64, // New reward amount, if the reward bit is set
+    pub timelock_delay: i64, // New timelock delay, if the timelock bit is set
+    pub eta: i64,      // Earliest unix timestamp the change can be executed
+    pub field_mask: u8, // UpdateAdminConfigPayload field bits being changed
+    pub allowlist_only: u8, // New allowlist flag, if the allowlist bit is set
+    pub bump: u8,
+    pub reward_mode: u8, // New RewardMode, if the reward mode bit is set
+    pub supply_cap: u64, // New supply cap, if the reward mode bit is set
+}
+
+impl AsRef<[u8]> for PendingChangeState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl PendingChangeState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8; // 8 bytes for field_mask, allowlist_only, bump and reward_mode to ensure memory alignment
+
+    // Size of version 2 and 3 pending changes, from before the supply cap, see `MigrateAccount`
+    pub const PRE_SUPPLY_CAP_LEN: usize = Self::LEN - 8;
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_layout_version(
+            &data,
+            Self::LEN,
+            Self::PRE_SUPPLY_CAP_LEN,
+            AccountType::PendingChange,
+        )?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const PendingChangeState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_change(
+        &mut self,
+        field_mask: u8,
+        reward_amount: u64,
+        timelock_delay: i64,
+        new_authority: Pubkey,
+        moderation_destination: Pubkey,
+    ) -> Result<(), ProgramError> {
+        if timelock_delay < 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        self.field_mask = field_mask;
+        self.reward_amount = reward_amount;
+        self.timelock_delay = timelock_delay;
+        self.new_authority = new_authority;
+        self.moderation_destination = moderation_destination;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_allowlist_only(&mut self, allowlist_only: bool) -> Result<(), ProgramError> {
+        self.allowlist_only = allowlist_only as u8;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_reward_mode(
+        &mut self,
+        reward_mode: RewardMode,
+        supply_cap: u64,
+    ) -> Result<(), ProgramError> {
+        self.reward_mode = reward_mode as u8;
+        self.supply_cap = supply_cap;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        admin: Pubkey,
+        payer: Pubkey,
+        eta: i64,
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::PendingChange),
+            admin,
+            payer,
+            new_authority: Pubkey::default(),
+            moderation_destination: Pubkey::default(),
+            reward_amount: 0,
+            timelock_delay: 0,
+            eta,
+            field_mask: 0,
+            allowlist_only: 0,
+            bump,
+            reward_mode: 0,
+            supply_cap: 0,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeratorState {
+    pub header: AccountHeader,
+    pub admin: Pubkey,     // Admin account the moderator acts for
+    pub moderator: Pubkey, // Wallet granted the moderator role
+    pub bump: u8,
+}
+
+impl AsRef<[u8]> for ModeratorState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl ModeratorState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 32 + 1;
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_version(&data, Self::LEN, AccountType::Moderator)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const ModeratorState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(admin: Pubkey, moderator: Pubkey, bump: u8) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Moderator),
+            admin,
+            moderator,
             bump,
         })
     }
 }
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BanState {
+    pub header: AccountHeader,
+    pub admin: Pubkey,  // Admin account the ban applies to
+    pub user: Pubkey,   // Banned wallet
+    pub payer: Pubkey,  // Receives the rent back when the ban is lifted
+    pub banned_at: i64, // Unix timestamp of the ban
+    pub reason: u8,     // Reason code given by the moderator
+    pub bump: u8,
+}
+
+impl AsRef<[u8]> for BanState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl BanState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 32 + 32 + 8 + 8; // 8 bytes for reason and bump to ensure memory alignment
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_version(&data, Self::LEN, AccountType::Ban)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const BanState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        admin: Pubkey,
+        user: Pubkey,
+        payer: Pubkey,
+        banned_at: i64,
+        reason: u8,
+        bump: u8,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Ban),
+            admin,
+            user,
+            payer,
+            banned_at,
+            reason,
+            bump,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowlistState {
+    pub header: AccountHeader,
+    pub admin: Pubkey, // Admin account the entry belongs to
+    pub user: Pubkey,  // Wallet allowed to rate while the catalog is allowlist-only
+    pub bump: u8,
+    pub payer: Pubkey, // Receives the rent back when the entry is removed, zeroed on migrated entries
+}
+
+impl AsRef<[u8]> for AllowlistState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl AllowlistState {
+    pub const LEN: usize = AccountHeader::LEN + 32 + 32 + 1 + 32;
+
+    // Size of version 3 entries, from before the payer was stored, see `MigrateAccount`
+    pub const PRE_PAYER_LEN: usize = Self::LEN - 32;
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_layout_version(
+            &data,
+            Self::LEN,
+            Self::PRE_PAYER_LEN,
+            AccountType::Allowlist,
+        )?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const AllowlistState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        admin: Pubkey,
+        user: Pubkey,
+        bump: u8,
+        payer: Pubkey,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::Allowlist),
+            admin,
+            user,
+            bump,
+            payer,
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClosedAdminState {
+    pub header: AccountHeader,
+    pub authority: Pubkey, // Last admin authority, can still clean up the catalog's accounts
+}
+
+impl AsRef<[u8]> for ClosedAdminState {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+}
+
+impl ClosedAdminState {
+    pub const LEN: usize = AccountHeader::LEN + 32;
+
+    #[inline(always)]
+    pub fn load(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data: Ref<'_, [u8]> = account.try_borrow_data()?;
+        check_version(&data, Self::LEN, AccountType::ClosedAdmin)?;
+
+        Ok(Ref::map(data, |data: &[u8]| unsafe {
+            &*(data.as_ptr() as *const ClosedAdminState)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(authority: Pubkey) -> Result<Self, ProgramError> {
+        Ok(Self {
+            header: AccountHeader::new(AccountType::ClosedAdmin),
+            authority,
+        })
+    }
+}
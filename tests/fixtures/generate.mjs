@@ -0,0 +1,168 @@
+// Writes the legacy accounts the test validator preloads with `--account-dir`, so the
+// migration tests can start from layouts this build can no longer create.
+// Run with `node tests/fixtures/generate.mjs` after changing an account below.
+import { createHash, createPrivateKey, createPublicKey } from "node:crypto";
+import { writeFileSync } from "node:fs";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const PROGRAM_ID = Buffer.from([
+	140, 255, 194, 33, 146, 45, 72, 116, 122, 130, 229, 197, 8, 112, 69, 144,
+	218, 130, 17, 87, 137, 101, 31, 81, 99, 98, 61, 84, 114, 77, 100, 212,
+]);
+
+// Private key of the legacy admin authority, the tests sign with the same key
+const LEGACY_AUTHORITY_SEED = Buffer.from(
+	Array.from({ length: 32 }, (_, i) => (i * 7 + 13) & 255)
+);
+
+const P = 2n ** 255n - 19n;
+const D = (-121665n * modPow(121666n, P - 2n)) % P;
+
+function modPow(base, exponent) {
+	let result = 1n;
+	base = ((base % P) + P) % P;
+	while (exponent > 0n) {
+		if (exponent & 1n) result = (result * base) % P;
+		base = (base * base) % P;
+		exponent >>= 1n;
+	}
+	return result;
+}
+
+// A PDA must not decompress to an ed25519 point
+function isOnCurve(bytes) {
+	let y = 0n;
+	for (let i = 31; i >= 0; i--) y = (y << 8n) | BigInt(bytes[i]);
+	y &= (1n << 255n) - 1n;
+	if (y >= P) return false;
+
+	let y2 = (y * y) % P;
+	let u = (y2 - 1n + P) % P;
+	let v = (D * y2 + 1n) % P;
+	let x2 = (u * modPow(v, P - 2n)) % P;
+	return x2 === 0n || modPow(x2, (P - 1n) / 2n) === 1n;
+}
+
+function findProgramAddress(seeds) {
+	for (let bump = 255; bump >= 0; bump--) {
+		let hash = createHash("sha256")
+			.update(Buffer.concat([...seeds, Buffer.from([bump]), PROGRAM_ID]))
+			.update("ProgramDerivedAddress")
+			.digest();
+		if (!isOnCurve(hash)) return [hash, bump];
+	}
+	throw new Error("No bump found");
+}
+
+function base58(bytes) {
+	const alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+	let value = BigInt("0x" + Buffer.from(bytes).toString("hex"));
+	let encoded = "";
+	while (value > 0n) {
+		encoded = alphabet[Number(value % 58n)] + encoded;
+		value /= 58n;
+	}
+	for (let i = 0; i < bytes.length && bytes[i] === 0; i++) encoded = "1" + encoded;
+	return encoded;
+}
+
+function publicKeyFromSeed(seed) {
+	let privateKey = createPrivateKey({
+		key: Buffer.concat([Buffer.from("302e020100300506032b657004220420", "hex"), seed]),
+		format: "der",
+		type: "pkcs8",
+	});
+	return createPublicKey(privateKey).export({ format: "der", type: "spki" }).subarray(-32);
+}
+
+function adminPDA(catalogId) {
+	let catalogIdBytes = Buffer.alloc(8);
+	catalogIdBytes.writeBigUInt64LE(catalogId);
+	return findProgramAddress([Buffer.from("ratings_admin"), catalogIdBytes]);
+}
+
+// Same formula as the runtime's Rent::minimum_balance with the default rent
+const rentExempt = (len) => (128 + len) * 6960;
+
+function writeAccount(name, address, data) {
+	let account = {
+		pubkey: base58(address),
+		account: {
+			lamports: rentExempt(data.length),
+			data: [data.toString("base64"), "base64"],
+			owner: base58(PROGRAM_ID),
+			executable: false,
+			rentEpoch: 0,
+			space: data.length,
+		},
+	};
+	let directory = dirname(fileURLToPath(import.meta.url));
+	writeFileSync(join(directory, `${name}.json`), JSON.stringify(account, null, 2) + "\n");
+}
+
+const legacyAuthority = publicKeyFromSeed(LEGACY_AUTHORITY_SEED);
+
+// Version 1 admin from before the counters, with no header: AdminState without the
+// header, counters and supply cap
+{
+	let [address, bump] = adminPDA(100n);
+	let data = Buffer.alloc(392);
+	legacyAuthority.copy(data, 0);
+	data.writeBigUInt64LE(5_000_000_000n, 96); // reward_amount
+	data.writeBigUInt64LE(100n, 104); // catalog_id
+	data.writeUInt8(bump, 112);
+	data.set([1, 10, 1], 114); // rating_scale
+	legacyAuthority.copy(data, 352); // moderation_destination
+	writeAccount("pre-counters-admin", address, data);
+}
+
+// Version 2 admin from before the supply cap, its header has no account type
+{
+	let [address, bump] = adminPDA(101n);
+	let data = Buffer.alloc(424);
+	data.writeUInt8(2, 0); // version
+	legacyAuthority.copy(data, 8);
+	data.writeBigUInt64LE(5_000_000_000n, 104); // reward_amount
+	data.writeBigUInt64LE(101n, 112); // catalog_id
+	data.writeUInt8(bump, 120);
+	data.set([1, 10, 1], 122); // rating_scale
+	legacyAuthority.copy(data, 360); // moderation_destination
+	data.writeBigUInt64LE(3n, 400); // total_ratings
+	data.writeBigUInt64LE(15_000_000_000n, 408); // total_rewards_paid
+	data.writeBigUInt64LE(1n, 416); // active_raters
+	writeAccount("untyped-admin", address, data);
+
+	// Version 1 profile of the legacy authority in that catalog, with no header
+	let [userAddress, userBump] = findProgramAddress([
+		Buffer.from("user"),
+		address,
+		legacyAuthority,
+	]);
+	let userData = Buffer.alloc(80);
+	legacyAuthority.copy(userData, 0);
+	userData.writeBigUInt64LE(3n, 32); // rating_count
+	userData.writeBigUInt64LE(15_000_000_000n, 40); // total_rewards
+	userData.writeBigInt64LE(1_700_000_000n, 48); // first_rating_at
+	userData.writeBigInt64LE(1_700_086_400n, 56); // last_rating_at
+	userData.writeBigInt64LE(2n, 64); // reputation
+	userData.writeUInt8(userBump, 72);
+	writeAccount("headerless-user", userAddress, userData);
+}
+
+// Version 3 allowlist entry of the legacy authority in the main test catalog, from before
+// entries stored their payer
+{
+	let [adminAddress] = adminPDA(1n);
+	let [address, bump] = findProgramAddress([
+		Buffer.from("allowlist"),
+		adminAddress,
+		legacyAuthority,
+	]);
+	let data = Buffer.alloc(73);
+	data.set([3, 9], 0); // version, account_type
+	adminAddress.copy(data, 8);
+	legacyAuthority.copy(data, 40);
+	data.writeUInt8(bump, 72);
+	writeAccount("pre-payer-allowlist", address, data);
+}